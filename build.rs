@@ -2,12 +2,14 @@ extern crate bindgen;
 extern crate cc;
 extern crate num_cpus;
 extern crate pkg_config;
+extern crate serde_json;
 
+use std::collections::HashMap;
 use std::env;
 use std::fmt::Write as FmtWrite;
 use std::fs::{self, File};
 use std::io::{self, BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str;
 
@@ -82,6 +84,11 @@ impl ParseCallbacks for Callbacks {
 
         if _name.starts_with(ch_layout_prefix) {
             Some(IntKind::ULongLong)
+        } else if _name == "AV_NOPTS_VALUE" {
+            // INT64_MIN behind an int64_t cast, which cexpr can't evaluate
+            // as of writing (see AV_NOPTS_VALUE in src/avutil/util.rs); make
+            // sure it is an i64 should that change.
+            Some(IntKind::LongLong)
         } else if value >= i32::MIN as i64
             && value <= i32::MAX as i64
             && (_name.starts_with(codec_cap_prefix) || _name.starts_with(codec_flag_prefix))
@@ -195,6 +202,23 @@ fn get_ffmpet_target_os() -> String {
     }
 }
 
+// Returns the include directory of the AMD AMF SDK, i.e. the directory
+// containing AMF/core/Factory.h, if it can be found.
+fn find_amf_include() -> Option<PathBuf> {
+    let mut candidates = vec![
+        PathBuf::from("/usr/local/include"),
+        PathBuf::from("/usr/include"),
+    ];
+    if let Ok(amf_sdk_root) = env::var("AMF_SDK_ROOT") {
+        candidates.insert(0, PathBuf::from(amf_sdk_root).join("include"));
+    }
+    candidates.push(PathBuf::from(r"C:\ProgramData\AMD\AMF"));
+
+    candidates
+        .into_iter()
+        .find(|dir| dir.join("AMF/core/Factory.h").exists())
+}
+
 fn build() -> io::Result<()> {
     let source_dir = source();
 
@@ -245,6 +269,24 @@ fn build() -> io::Result<()> {
         configure.arg("--enable-stripping");
     }
 
+    // override configure's default optimization flags (-O3 for gcc/clang)
+    if let Ok(level) = env::var("FFMPEG_OPTIMIZE_LEVEL") {
+        match level.as_str() {
+            "0" | "1" | "2" | "3" | "s" => {
+                configure.arg(format!("--optflags=-O{}", level));
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "FFMPEG_OPTIMIZE_LEVEL must be one of 0, 1, 2, 3 or s, got {:?}",
+                        level
+                    ),
+                ));
+            }
+        }
+    }
+
     // make it static
     configure.arg("--enable-static");
     configure.arg("--disable-shared");
@@ -334,6 +376,7 @@ fn build() -> io::Result<()> {
         "BUILD_LIB_STAGEFRIGHT_H264",
         "libstagefright-h264"
     );
+    enable!(configure, "BUILD_LIB_SVTAV1", "libsvtav1");
     enable!(configure, "BUILD_LIB_THEORA", "libtheora");
     enable!(configure, "BUILD_LIB_TWOLAME", "libtwolame");
     enable!(configure, "BUILD_LIB_UTVIDEO", "libutvideo");
@@ -351,6 +394,56 @@ fn build() -> io::Result<()> {
     // other external libraries
     enable!(configure, "BUILD_LIB_DRM", "libdrm");
     enable!(configure, "BUILD_NVENC", "nvenc");
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("linux") {
+        enable!(configure, "BUILD_VDPAU", "vdpau");
+    }
+
+    // Intel QSV, either through the legacy Media SDK or through oneVPL
+    if env::var("CARGO_FEATURE_BUILD_LIB_LIBMFX").is_ok()
+        && env::var("CARGO_FEATURE_BUILD_LIB_VPL").is_ok()
+    {
+        println!(
+            "cargo:warning=build-lib-libmfx and build-lib-vpl are mutually exclusive, \
+             configure will reject enabling both"
+        );
+    }
+    enable!(configure, "BUILD_LIB_LIBMFX", "libmfx");
+    enable!(configure, "BUILD_LIB_VPL", "libvpl");
+    if env::var("CARGO_FEATURE_BUILD_LIB_VPL").is_ok() {
+        // on Linux the headers live in /usr/include/vpl and are found through
+        // pkg-config, the Windows SDK has to be pointed to explicitly
+        if let Ok(vpl_root) = env::var("VPL_ROOT") {
+            let vpl_root = PathBuf::from(vpl_root);
+            configure.arg(format!(
+                "--extra-cflags=-I{}",
+                vpl_root.join("include").to_string_lossy()
+            ));
+            configure.arg(format!(
+                "--extra-ldflags=-L{}",
+                vpl_root.join("lib").to_string_lossy()
+            ));
+        }
+    }
+
+    // AMD AMF is x86 only, and configure fails outright when the SDK headers
+    // are missing, so check for them first
+    if env::var("CARGO_FEATURE_BUILD_AMF").is_ok() {
+        let target_arch = env::var("CARGO_CFG_TARGET_ARCH");
+        if target_arch.as_deref() != Ok("x86_64") && target_arch.as_deref() != Ok("x86") {
+            println!("cargo:warning=AMF is only supported on x86/x86_64, not enabling it");
+        } else if let Some(amf_include) = find_amf_include() {
+            configure.arg("--enable-amf");
+            configure.arg(format!(
+                "--extra-cflags=-I{}",
+                amf_include.to_string_lossy()
+            ));
+        } else {
+            println!(
+                "cargo:warning=AMF SDK headers (AMF/core/Factory.h) not found, not enabling AMF; \
+                 set AMF_SDK_ROOT to the SDK location"
+            );
+        }
+    }
 
     // configure external protocols
     enable!(configure, "BUILD_LIB_SMBCLIENT", "libsmbclient");
@@ -359,10 +452,27 @@ fn build() -> io::Result<()> {
     // configure misc build options
     enable!(configure, "BUILD_PIC", "pic");
 
+    // User flags go last. configure accumulates repeated --extra-* options,
+    // so these add to the flags determined above rather than replace them.
+    for &(var, option) in &[
+        ("FFMPEG_CFLAGS", "--extra-cflags"),
+        ("FFMPEG_LDFLAGS", "--extra-ldflags"),
+    ] {
+        if let Ok(flags) = env::var(var) {
+            configure.arg(format!("{}={}", option, flags));
+        }
+    }
+
     // run ./configure
     let output = configure
         .output()
         .unwrap_or_else(|_| panic!("{:?} failed", configure));
+    // keep the full configure output around even if it succeeds
+    if let Ok(log) = env::var("FFMPEG_CONFIGURE_LOG") {
+        let mut log = File::create(log)?;
+        log.write_all(&output.stdout)?;
+        log.write_all(&output.stderr)?;
+    }
     if !output.status.success() {
         println!("configure: {}", String::from_utf8_lossy(&output.stdout));
 
@@ -376,13 +486,15 @@ fn build() -> io::Result<()> {
     }
 
     // run make
-    if !Command::new("make")
-        .arg("-j")
+    let mut make = Command::new("make");
+    make.arg("-j")
         .arg(num_cpus::get().to_string())
-        .current_dir(source())
-        .status()?
-        .success()
-    {
+        .current_dir(source());
+    if let Ok(log) = env::var("FFMPEG_MAKE_LOG") {
+        let log = File::create(log)?;
+        make.stderr(log.try_clone()?).stdout(log);
+    }
+    if !make.status()?.success() {
         return Err(io::Error::new(io::ErrorKind::Other, "make failed"));
     }
 
@@ -399,6 +511,85 @@ fn build() -> io::Result<()> {
     Ok(())
 }
 
+/// (major, minor) of MACOSX_DEPLOYMENT_TARGET, if set.
+fn macos_deployment_target() -> Option<(u32, u32)> {
+    let target = env::var("MACOSX_DEPLOYMENT_TARGET").ok()?;
+    let mut parts = target.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |minor| minor.parse().ok())?;
+    Some((major, minor))
+}
+
+// # Nix/NixOS
+//
+// nixpkgs installs FFmpeg under /nix/store/<hash>-ffmpeg-<version>/. Inside a
+// nix shell the pkg-config fallback usually finds it already; otherwise
+// FFMPEG_NIX_STORE_PATH can name a store path with include/ and lib/
+// directories, which is then used exactly like FFMPEG_DIR. NIX_BUILD_TOP and
+// buildInputs change whenever the derivations in scope do, so they are
+// tracked to rebuild against an updated FFmpeg.
+fn prebuilt_ffmpeg_dir() -> Option<PathBuf> {
+    env::var_os("FFMPEG_DIR")
+        .or_else(|| env::var_os("FFMPEG_NIX_STORE_PATH"))
+        .map(PathBuf::from)
+}
+
+// # vcpkg
+//
+// Besides the MSVC-only try_vcpkg lookup, FFMPEG_DIR may point at a vcpkg
+// installed tree (or a manifest-mode project containing vcpkg_installed/), in
+// which case the triplet subdirectory is used as the prefix. This covers MinGW
+// and cross builds that vcpkg-rs does not handle.
+fn default_vcpkg_triplet(statik: bool) -> Option<String> {
+    let arch = match env::var("CARGO_CFG_TARGET_ARCH").ok()?.as_str() {
+        "x86_64" => "x64",
+        "x86" => "x86",
+        "aarch64" => "arm64",
+        "arm" => "arm",
+        _ => return None,
+    };
+    let os = env::var("CARGO_CFG_TARGET_OS").ok()?;
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+
+    let suffix = match (os.as_str(), target_env.as_str()) {
+        ("windows", "gnu") if statik => "mingw-static",
+        ("windows", "gnu") => "mingw-dynamic",
+        ("windows", _) if statik => "windows-static",
+        ("windows", _) => "windows",
+        ("linux", _) => "linux",
+        ("macos", _) => "osx",
+        _ => return None,
+    };
+
+    Some(format!("{}-{}", arch, suffix))
+}
+
+/// Triplet directory to use instead of `ffmpeg_dir` if it is a vcpkg tree.
+/// VCPKG_TRIPLET overrides the triplet derived from the target.
+fn vcpkg_triplet_dir(ffmpeg_dir: &Path, statik: bool) -> Option<PathBuf> {
+    let installed = if ffmpeg_dir.join("vcpkg_installed").is_dir() {
+        ffmpeg_dir.join("vcpkg_installed")
+    } else if ffmpeg_dir.join("vcpkg").is_dir() || ffmpeg_dir.join("CONTROL").is_file() {
+        ffmpeg_dir.to_path_buf()
+    } else {
+        return None;
+    };
+
+    let triplet = env::var("VCPKG_TRIPLET")
+        .ok()
+        .or_else(|| default_vcpkg_triplet(statik))?;
+    let triplet_dir = installed.join(&triplet);
+    if triplet_dir.join("include").is_dir() {
+        Some(triplet_dir)
+    } else {
+        println!(
+            "cargo:warning=FFMPEG_DIR looks like a vcpkg tree but has no {} triplet, set VCPKG_TRIPLET",
+            triplet
+        );
+        None
+    }
+}
+
 #[cfg(not(target_env = "msvc"))]
 fn try_vcpkg(_statik: bool) -> Option<Vec<PathBuf>> {
     None
@@ -406,11 +597,19 @@ fn try_vcpkg(_statik: bool) -> Option<Vec<PathBuf>> {
 
 #[cfg(target_env = "msvc")]
 fn try_vcpkg(statik: bool) -> Option<Vec<PathBuf>> {
+    // vcpkg-rs picks a static triplet unless told otherwise, and refuses
+    // dynamic triplets without VCPKGRS_DYNAMIC.
     if !statik {
         env::set_var("VCPKGRS_DYNAMIC", "1");
     }
 
-    vcpkg::find_package("ffmpeg")
+    let mut config = vcpkg::Config::new();
+    if let Ok(triplet) = env::var("VCPKG_TRIPLET") {
+        config.target_triplet(triplet);
+    }
+
+    config
+        .find_package("ffmpeg")
         .map_err(|e| {
             println!("Could not find ffmpeg with vcpkg: {}", e);
         })
@@ -418,20 +617,117 @@ fn try_vcpkg(statik: bool) -> Option<Vec<PathBuf>> {
         .ok()
 }
 
-fn check_features(
-    include_paths: Vec<PathBuf>,
+// # pkg-config
+//
+// When cross-compiling, PKG_CONFIG_PATH (or one of the HOST_/TARGET_/<target>
+// variants pkg-config-rs looks at) may point at .pc files of the host, e.g.
+// with both host and target derivations in scope on Nix. With
+// FFMPEG_PKGCONFIG_SYSTEM_ONLY=1 these are all dropped, so only the default
+// search path of the pkg-config binary is used; PKG_CONFIG_LIBDIR and
+// PKG_CONFIG_SYSROOT_DIR are left alone since they are how a cross
+// pkg-config is normally set up. pkg-config-rs also refuses to run for cross
+// targets unless PKG_CONFIG, PKG_CONFIG_SYSROOT_DIR or PKG_CONFIG_ALLOW_CROSS
+// is set; FFMPEG_PKGCONFIG_ALLOW_CROSS=1 lifts that guard for setups where
+// the default pkg-config already knows about the target.
+fn pkg_config(statik: bool) -> pkg_config::Config {
+    if env::var("FFMPEG_PKGCONFIG_SYSTEM_ONLY").as_deref() == Ok("1") {
+        let target = env::var("TARGET").unwrap_or_default();
+        for var in &[
+            format!("PKG_CONFIG_PATH_{}", target),
+            format!("PKG_CONFIG_PATH_{}", target.replace('-', "_")),
+            "HOST_PKG_CONFIG_PATH".to_string(),
+            "TARGET_PKG_CONFIG_PATH".to_string(),
+            "PKG_CONFIG_PATH".to_string(),
+        ] {
+            env::remove_var(var);
+        }
+    }
+
+    if env::var("FFMPEG_PKGCONFIG_ALLOW_CROSS").as_deref() == Ok("1") {
+        env::set_var("PKG_CONFIG_ALLOW_CROSS", "1");
+    }
+
+    let mut config = pkg_config::Config::new();
+    config.statik(statik);
+    config
+}
+
+// (lib, begin major, end major, begin minor, end minor) of the
+// {lib}_version_greater_than_{major}_{minor} checks
+const VERSION_CHECK_INFO: [(&str, u32, u32, u32, u32); 1] = [("avcodec", 56, 62, 0, 108)];
+
+// (lib, feature, major version minus the libavcodec one) of the
+// {lib}_version_matches_avcodec checks; these offsets have held for every
+// release since FFmpeg 3.0
+const VERSION_CONSISTENCY_INFO: [(&str, Option<&str>, i32); 3] = [
+    ("avutil", None, -2),
+    ("avformat", Some("avformat"), 0),
+    ("swscale", Some("swscale"), -53),
+];
+
+fn enabled_feature_infos(
     infos: &[(&'static str, Option<&'static str>, &'static str)],
-) {
-    let mut includes_code = String::new();
-    let mut main_code = String::new();
+) -> Vec<(&'static str, &'static str)> {
+    infos
+        .iter()
+        .filter(|&&(_, feature, _)| match feature {
+            Some(feature) => env::var(format!("CARGO_FEATURE_{}", feature.to_uppercase())).is_ok(),
+            None => true,
+        })
+        .map(|&(header, _, var)| (header, var))
+        .collect()
+}
+
+/// Libraries whose headers are checked to come from the same FFmpeg release
+/// as the libavcodec ones, along with the expected major version offset.
+fn version_consistency_infos() -> Vec<(&'static str, i32)> {
+    if env::var("CARGO_FEATURE_AVCODEC").is_err() {
+        return Vec::new();
+    }
+
+    VERSION_CONSISTENCY_INFO
+        .iter()
+        .filter(|&&(_, feature, _)| match feature {
+            Some(feature) => env::var(format!("CARGO_FEATURE_{}", feature.to_uppercase())).is_ok(),
+            None => true,
+        })
+        .map(|&(lib, _, offset)| (lib, offset))
+        .collect()
+}
 
-    for &(header, feature, var) in infos {
-        if let Some(feature) = feature {
-            if env::var(format!("CARGO_FEATURE_{}", feature.to_uppercase())).is_err() {
-                continue;
+fn version_check_keys() -> Vec<String> {
+    let mut keys = Vec::new();
+    for &(lib, begin_version_major, end_version_major, begin_version_minor, end_version_minor) in
+        VERSION_CHECK_INFO.iter()
+    {
+        for version_major in begin_version_major..end_version_major {
+            for version_minor in begin_version_minor..end_version_minor {
+                keys.push(format!(
+                    "{lib}_version_greater_than_{version_major}_{version_minor}",
+                    lib = lib,
+                    version_major = version_major,
+                    version_minor = version_minor
+                ));
             }
         }
+    }
+    for (lib, _) in version_consistency_infos() {
+        keys.push(format!("{}_version_matches_avcodec", lib));
+    }
+    keys
+}
+
+/// Compiles and runs check.c, returning whether each macro is non-zero,
+/// whether it is defined at all (`<var>_is_defined`) and the result of each
+/// version check.
+fn run_feature_check(
+    include_paths: Vec<PathBuf>,
+    infos: &[(&'static str, &'static str)],
+) -> HashMap<String, bool> {
+    let mut includes_code = String::new();
+    let mut main_code = String::new();
 
+    for &(header, var) in infos {
         let include = format!("#include <{}>", header);
         if !includes_code.contains(&include) {
             includes_code.push_str(&include);
@@ -460,9 +756,8 @@ fn check_features(
         );
     }
 
-    let version_check_info = [("avcodec", 56, 62, 0, 108)];
     for &(lib, begin_version_major, end_version_major, begin_version_minor, end_version_minor) in
-        version_check_info.iter()
+        VERSION_CHECK_INFO.iter()
     {
         for version_major in begin_version_major..end_version_major {
             for version_minor in begin_version_minor..end_version_minor {
@@ -479,6 +774,22 @@ fn check_features(
         }
     }
 
+    for (lib, offset) in version_consistency_infos() {
+        let include = format!("#include <lib{}/version.h>", lib);
+        if !includes_code.contains(&include) {
+            includes_code.push_str(&include);
+            includes_code.push('\n');
+        }
+        let _ = write!(
+            main_code,
+            r#"printf("[{lib}_version_matches_avcodec]%d\n", LIB{lib_uppercase}_VERSION_MAJOR == LIBAVCODEC_VERSION_MAJOR + ({offset}));
+            "#,
+            lib = lib,
+            lib_uppercase = lib.to_uppercase(),
+            offset = offset
+        );
+    }
+
     let out_dir = output();
 
     write!(
@@ -537,30 +848,105 @@ fn check_features(
 
     println!("stdout of {}={}", executable.display(), stdout);
 
-    for &(_, feature, var) in infos {
-        if let Some(feature) = feature {
-            if env::var(format!("CARGO_FEATURE_{}", feature.to_uppercase())).is_err() {
-                continue;
+    let mut features = HashMap::new();
+
+    for &(_, var) in infos {
+        let var_str = format!("[{var}]", var = var);
+        let pos = var_str.len()
+            + stdout
+                .find(&var_str)
+                .unwrap_or_else(|| panic!("Variable '{}' not found in stdout output", var_str));
+        features.insert(var.to_string(), &stdout[pos..pos + 1] == "1");
+        features.insert(
+            format!("{}_is_defined", var),
+            &stdout[pos + 1..pos + 2] == "1",
+        );
+    }
+
+    for key in version_check_keys() {
+        let search_str = format!("[{}]", key);
+        let pos = stdout
+            .find(&search_str)
+            .expect("Variable not found in output")
+            + search_str.len();
+        features.insert(key, &stdout[pos..pos + 1] == "1");
+    }
+
+    features
+}
+
+/// Results of a previous `run_feature_check` from the features cache, if it
+/// has all of `keys`.
+fn cached_features(cache_file: &Path, keys: &[String]) -> Option<HashMap<String, bool>> {
+    let file = File::open(cache_file).ok()?;
+    let features: HashMap<String, bool> = serde_json::from_reader(BufReader::new(file)).ok()?;
+
+    if keys.iter().all(|key| features.contains_key(key)) {
+        Some(features)
+    } else {
+        None
+    }
+}
+
+fn check_features(
+    include_paths: Vec<PathBuf>,
+    infos: &[(&'static str, Option<&'static str>, &'static str)],
+) {
+    let infos = enabled_feature_infos(infos);
+
+    // FFMPEG_SKIP_CHECK_FEATURES reuses the results of the last check, which
+    // is only correct as long as the FFmpeg installation stays the same.
+    let cache_file = env::var_os("FFMPEG_FEATURES_CACHE_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| output().join("features.json"));
+    let mut keys = version_check_keys();
+    for &(_, var) in &infos {
+        keys.push(var.to_string());
+        keys.push(format!("{}_is_defined", var));
+    }
+
+    let cached = if env::var("FFMPEG_SKIP_CHECK_FEATURES").is_ok() {
+        let cached = cached_features(&cache_file, &keys);
+        if cached.is_none() {
+            println!(
+                "cargo:warning=FFMPEG_SKIP_CHECK_FEATURES is set but {} is missing or \
+                 incomplete, checking features anyway",
+                cache_file.display()
+            );
+        }
+        cached
+    } else {
+        None
+    };
+    let features = cached.unwrap_or_else(|| {
+        let features = run_feature_check(include_paths, &infos);
+        match File::create(&cache_file) {
+            Ok(file) => {
+                serde_json::to_writer(file, &features).expect("Failed to write features cache")
             }
+            Err(e) => println!(
+                "cargo:warning=could not write features cache {}: {}",
+                cache_file.display(),
+                e
+            ),
         }
+        features
+    });
+
+    for &(_, var) in &infos {
         // Here so the features are listed for rust-ffmpeg at build time. Does
         // NOT represent activated features, just features that exist (hence the
         // lack of "=true" at the end)
         println!(r#"cargo:{}="#, var);
 
-        let var_str = format!("[{var}]", var = var);
-        let pos = var_str.len()
-            + stdout
-                .find(&var_str)
-                .unwrap_or_else(|| panic!("Variable '{}' not found in stdout output", var_str));
-        if &stdout[pos..pos + 1] == "1" {
+        if features[var] {
             println!(r#"cargo:rustc-cfg=feature="{}""#, var.to_lowercase());
             println!(r#"cargo:{}=true"#, var.to_lowercase());
         }
 
         // Also find out if defined or not (useful for cases where only the definition of a macro
         // can be used as distinction)
-        if &stdout[pos + 1..pos + 2] == "1" {
+        if features[&format!("{}_is_defined", var)] {
             println!(
                 r#"cargo:rustc-cfg=feature="{}_is_defined""#,
                 var.to_lowercase()
@@ -569,30 +955,10 @@ fn check_features(
         }
     }
 
-    for &(lib, begin_version_major, end_version_major, begin_version_minor, end_version_minor) in
-        version_check_info.iter()
-    {
-        for version_major in begin_version_major..end_version_major {
-            for version_minor in begin_version_minor..end_version_minor {
-                let search_str = format!(
-                    "[{lib}_version_greater_than_{version_major}_{version_minor}]",
-                    version_major = version_major,
-                    version_minor = version_minor,
-                    lib = lib
-                );
-                let pos = stdout
-                    .find(&search_str)
-                    .expect("Variable not found in output")
-                    + search_str.len();
-
-                if &stdout[pos..pos + 1] == "1" {
-                    println!(
-                        r#"cargo:rustc-cfg=feature="{}""#,
-                        &search_str[1..(search_str.len() - 1)]
-                    );
-                    println!(r#"cargo:{}=true"#, &search_str[1..(search_str.len() - 1)]);
-                }
-            }
+    for key in version_check_keys() {
+        if features[&key] {
+            println!(r#"cargo:rustc-cfg=feature="{}""#, key);
+            println!(r#"cargo:{}=true"#, key);
         }
     }
 
@@ -614,39 +980,64 @@ fn check_features(
         ("ffmpeg_7_0", 61, 3),
         ("ffmpeg_7_1", 61, 19),
     ];
+    let mut detected_version_flag = None;
     for &(ffmpeg_version_flag, lavc_version_major, lavc_version_minor) in
         ffmpeg_lavc_versions.iter()
     {
-        let search_str = format!(
-            "[avcodec_version_greater_than_{lavc_version_major}_{lavc_version_minor}]",
+        let key = format!(
+            "avcodec_version_greater_than_{lavc_version_major}_{lavc_version_minor}",
             lavc_version_major = lavc_version_major,
             lavc_version_minor = lavc_version_minor - 1
         );
-        let pos = stdout
-            .find(&search_str)
-            .expect("Variable not found in output")
-            + search_str.len();
-        if &stdout[pos..pos + 1] == "1" {
+        if features[&key] {
             println!(r#"cargo:rustc-cfg=feature="{}""#, ffmpeg_version_flag);
             println!(r#"cargo:{}=true"#, ffmpeg_version_flag);
+            detected_version_flag = Some(ffmpeg_version_flag);
         } else {
             println!(r#"cargo:{}="#, ffmpeg_version_flag);
         }
     }
+
+    // Headers of different releases mixed together, e.g. a partially updated
+    // installation or include paths of two installations, make the bindings
+    // disagree with the actual struct layouts, which only shows up as crashes
+    // at runtime.
+    for (lib, _) in version_consistency_infos() {
+        if !features[&format!("{}_version_matches_avcodec", lib)] {
+            panic!(
+                "The lib{} headers do not belong to the same FFmpeg release as the libavcodec \
+                 headers (detected as {}). Make sure all FFmpeg headers come from a single \
+                 installation.",
+                lib,
+                detected_version_flag.unwrap_or("an FFmpeg release older than 3.0")
+            );
+        }
+    }
 }
 
 fn search_include(include_paths: &[PathBuf], header: &str) -> String {
+    // Headers passed to bindgen must exist, or clang fails with an unhelpful
+    // "file not found" error deep inside the bindgen output.
+    maybe_search_include(include_paths, header).unwrap_or_else(|| {
+        let lib = header.split('/').next().unwrap_or(header);
+        panic!(
+            "Required FFmpeg header not found: {}. Searched {:?} and /usr/include. \
+             Make sure the FFmpeg development headers ({}-dev or ffmpeg-dev) are installed, \
+             or point FFMPEG_DIR or PKG_CONFIG_PATH at your FFmpeg installation.",
+            header, include_paths, lib
+        )
+    })
+}
+
+fn maybe_search_include(include_paths: &[PathBuf], header: &str) -> Option<String> {
     for dir in include_paths {
         let include = dir.join(header);
         if fs::metadata(&include).is_ok() {
-            return include.as_path().to_str().unwrap().to_string();
+            return Some(include.as_path().to_str().unwrap().to_string());
         }
     }
-    format!("/usr/include/{}", header)
-}
 
-fn maybe_search_include(include_paths: &[PathBuf], header: &str) -> Option<String> {
-    let path = search_include(include_paths, header);
+    let path = format!("/usr/include/{}", header);
     if fs::metadata(&path).is_ok() {
         Some(path)
     } else {
@@ -668,6 +1059,29 @@ fn link_to_libraries(statik: bool) {
 }
 
 fn main() {
+    // Any rerun-if directive replaces cargo's default of rerunning on every
+    // package change, so the files read from the package are listed too.
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=channel_layout_fixed.h");
+    println!("cargo:rerun-if-env-changed=FFMPEG_DIR");
+    println!("cargo:rerun-if-env-changed=FFMPEG_NIX_STORE_PATH");
+    println!("cargo:rerun-if-env-changed=NIX_BUILD_TOP");
+    println!("cargo:rerun-if-env-changed=buildInputs");
+    println!("cargo:rerun-if-env-changed=FFMPEG_OPTIMIZE_LEVEL");
+    println!("cargo:rerun-if-env-changed=FFMPEG_CONFIGURE_LOG");
+    println!("cargo:rerun-if-env-changed=FFMPEG_MAKE_LOG");
+    println!("cargo:rerun-if-env-changed=MACOSX_DEPLOYMENT_TARGET");
+    println!("cargo:rerun-if-env-changed=FFMPEG_CFLAGS");
+    println!("cargo:rerun-if-env-changed=FFMPEG_LDFLAGS");
+    println!("cargo:rerun-if-env-changed=FFMPEG_SKIP_CHECK_FEATURES");
+    println!("cargo:rerun-if-env-changed=FFMPEG_FEATURES_CACHE_FILE");
+    println!("cargo:rerun-if-env-changed=VCPKG_TRIPLET");
+    println!("cargo:rerun-if-env-changed=FFMPEG_PKGCONFIG_SYSTEM_ONLY");
+    println!("cargo:rerun-if-env-changed=FFMPEG_PKGCONFIG_ALLOW_CROSS");
+    println!("cargo:rerun-if-env-changed=AMF_SDK_ROOT");
+    println!("cargo:rerun-if-env-changed=VPL_ROOT");
+    println!("cargo:rerun-if-env-changed=CUDA_PATH");
+
     let statik = env::var("CARGO_FEATURE_STATIC").is_ok();
     let ffmpeg_major_version: u32 = env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap();
 
@@ -737,8 +1151,8 @@ fn main() {
         vec![search().join("include")]
     }
     // Use prebuilt library
-    else if let Ok(ffmpeg_dir) = env::var("FFMPEG_DIR") {
-        let ffmpeg_dir = PathBuf::from(ffmpeg_dir);
+    else if let Some(ffmpeg_dir) = prebuilt_ffmpeg_dir() {
+        let ffmpeg_dir = vcpkg_triplet_dir(&ffmpeg_dir, statik).unwrap_or(ffmpeg_dir);
         if ffmpeg_dir.join("lib/amd64").exists()
             && env::var("CARGO_CFG_TARGET_ARCH").as_deref() == Ok("x86_64")
         {
@@ -789,10 +1203,7 @@ fn main() {
     }
     // Fallback to pkg-config
     else {
-        pkg_config::Config::new()
-            .statik(statik)
-            .probe("libavutil")
-            .unwrap();
+        pkg_config(statik).probe("libavutil").unwrap();
 
         let mut libs = vec![
             ("libavformat", "AVFORMAT"),
@@ -807,39 +1218,41 @@ fn main() {
 
         for (lib_name, env_variable_name) in libs.iter() {
             if env::var(format!("CARGO_FEATURE_{}", env_variable_name)).is_ok() {
-                pkg_config::Config::new()
-                    .statik(statik)
-                    .probe(lib_name)
-                    .unwrap();
+                pkg_config(statik).probe(lib_name).unwrap();
             }
         }
 
-        pkg_config::Config::new()
-            .statik(statik)
+        pkg_config(statik)
             .probe("libavcodec")
             .unwrap()
             .include_paths
     };
 
-    if statik && cfg!(target_os = "macos") {
-        let frameworks = vec![
+    if statik && env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("macos") {
+        let mut frameworks = vec![
             "AppKit",
             "AudioToolbox",
             "AVFoundation",
+            "CoreAudio",
             "CoreFoundation",
             "CoreGraphics",
             "CoreMedia",
+            "CoreMIDI",
             "CoreServices",
             "CoreVideo",
             "Foundation",
             "OpenCL",
             "OpenGL",
-            "QTKit",
             "QuartzCore",
             "Security",
-            "VideoDecodeAcceleration",
             "VideoToolbox",
         ];
+        // QTKit and VideoDecodeAcceleration are gone from the 10.15 SDK
+        // onwards, only link them when explicitly targeting older releases.
+        if macos_deployment_target().is_some_and(|target| target < (10, 15)) {
+            frameworks.push("QTKit");
+            frameworks.push("VideoDecodeAcceleration");
+        }
         for f in frameworks {
             println!("cargo:rustc-link-lib=framework={}", f);
         }
@@ -1245,6 +1658,12 @@ fn main() {
         .blocklist_function("y1l")
         .blocklist_function("ynl")
         .opaque_type("__mingw_ldbl_type_t")
+        // __int128 only exists on 64-bit targets (x86_64, aarch64, riscv64,
+        // ...) and i128 only has the same alignment as it since Rust 1.77 on
+        // x86_64. No FFmpeg API takes or returns one, they come from system
+        // headers, so keep them opaque instead of emitting i128/u128.
+        .opaque_type("__int128_t")
+        .opaque_type("__uint128_t")
         .default_enum_style(bindgen::EnumVariation::Rust {
             non_exhaustive: env::var("CARGO_FEATURE_NON_EXHAUSTIVE_ENUMS").is_ok(),
         })
@@ -1265,6 +1684,11 @@ fn main() {
         if ffmpeg_major_version < 5 {
             builder = builder.header(search_include(&include_paths, "libavcodec/vaapi.h"))
         }
+
+        // split out of avcodec.h in FFmpeg 4.3
+        if let Some(codec_header) = maybe_search_include(&include_paths, "libavcodec/codec.h") {
+            builder = builder.header(codec_header);
+        }
     }
 
     if env::var("CARGO_FEATURE_AVDEVICE").is_ok() {
@@ -1363,6 +1787,28 @@ fn main() {
         builder = builder.header(hwcontext_drm_header);
     }
 
+    // hwcontext_vdpau.h pulls in <vdpau/vdpau.h> from libvdpau, only bind it
+    // when both are around
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("linux") {
+        if let (Some(hwcontext_vdpau_header), Some(_)) = (
+            maybe_search_include(&include_paths, "libavutil/hwcontext_vdpau.h"),
+            maybe_search_include(&include_paths, "vdpau/vdpau.h"),
+        ) {
+            builder = builder.header(hwcontext_vdpau_header);
+        }
+    }
+
+    // hwcontext_cuda.h includes cuda.h from the CUDA toolkit
+    if env::var("CARGO_FEATURE_HWCONTEXT_CUDA").is_ok() {
+        let cuda_include = match env::var("CUDA_PATH") {
+            Ok(cuda_path) => PathBuf::from(cuda_path).join("include"),
+            Err(_) => PathBuf::from("/usr/local/cuda/include"),
+        };
+        builder = builder
+            .clang_arg(format!("-I{}", cuda_include.to_string_lossy()))
+            .header(search_include(&include_paths, "libavutil/hwcontext_cuda.h"));
+    }
+
     // Finish the builder and generate the bindings.
     let bindings = builder
         .generate()
@@ -0,0 +1,81 @@
+use libc::{c_int, EINVAL};
+use {avcodec_fill_audio_frame, samples_get_buffer_size, AVFrame, AVSampleFormat};
+
+use avutil::check;
+use {FFmpegError, AVERROR};
+
+/// Points the data and linesize fields of `frame` into `buf`, laid out as
+/// `nb_channels` channels of `frame->nb_samples` samples each, which has to
+/// be set beforehand.
+///
+/// Nothing is copied: `buf` must outlive every use of the frame's data, and
+/// the frame must not be written through. `buf` has to be at least
+/// `audio_frame_buffer_size` bytes.
+pub unsafe fn fill_audio_frame(
+    frame: *mut AVFrame,
+    nb_channels: c_int,
+    sample_fmt: AVSampleFormat,
+    buf: &[u8],
+    align: c_int,
+) -> Result<(), FFmpegError> {
+    if buf.len() > c_int::MAX as usize {
+        return Err(FFmpegError::Other(AVERROR(EINVAL)));
+    }
+
+    check(avcodec_fill_audio_frame(
+        frame,
+        nb_channels,
+        sample_fmt,
+        buf.as_ptr(),
+        buf.len() as c_int,
+        align,
+    ))?;
+    Ok(())
+}
+
+/// Size of the buffer `fill_audio_frame` needs for `nb_samples` samples.
+pub fn audio_frame_buffer_size(
+    nb_channels: c_int,
+    sample_fmt: AVSampleFormat,
+    nb_samples: c_int,
+    align: c_int,
+) -> Result<usize, FFmpegError> {
+    samples_get_buffer_size(nb_channels, nb_samples, sample_fmt, align)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {av_frame_alloc, av_frame_free};
+
+    #[test]
+    fn planar_stereo() {
+        let size = audio_frame_buffer_size(2, AVSampleFormat::AV_SAMPLE_FMT_FLTP, 1024, 0).unwrap();
+        assert_eq!(size, 2 * 1024 * 4);
+        let buf = vec![0u8; size];
+
+        unsafe {
+            let mut frame = av_frame_alloc();
+            (*frame).nb_samples = 1024;
+            fill_audio_frame(frame, 2, AVSampleFormat::AV_SAMPLE_FMT_FLTP, &buf, 0).unwrap();
+
+            let data = (*frame).data;
+            assert_ne!(data[0], data[1]);
+            assert_eq!(data[0] as *const u8, buf.as_ptr());
+            assert_eq!(data[1] as usize - data[0] as usize, 1024 * 4);
+            assert_eq!((*frame).linesize[0], 1024 * 4);
+
+            // one byte short
+            assert!(fill_audio_frame(
+                frame,
+                2,
+                AVSampleFormat::AV_SAMPLE_FMT_FLTP,
+                &buf[..size - 1],
+                0
+            )
+            .is_err());
+
+            av_frame_free(&mut frame);
+        }
+    }
+}
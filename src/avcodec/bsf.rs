@@ -0,0 +1,165 @@
+use std::ffi::CString;
+use std::ptr;
+
+use {
+    av_bsf_alloc, av_bsf_free, av_bsf_get_by_name, av_bsf_init, av_bsf_list_parse_str,
+    av_bsf_receive_packet, av_bsf_send_packet, AVBSFContext, AVCodecParameters, AVPacket,
+    AVRational,
+};
+
+use avutil::check;
+use {FFmpegError, AVERROR_BSF_NOT_FOUND};
+
+/// Owned bitstream filter context, freed with `av_bsf_free` on drop.
+///
+/// After construction, fill in `par_in` and `time_base_in` from the input
+/// stream, then call `init`.
+pub struct BsfContext(*mut AVBSFContext);
+
+// SAFETY: AVBSFContext has no thread affinity and is only accessed through
+// BsfContext, which requires &mut self for every operation mutating it;
+// see the safety_notes module.
+unsafe impl Send for BsfContext {}
+
+impl BsfContext {
+    pub fn new(filter_name: &str) -> Result<Self, FFmpegError> {
+        let filter_name = CString::new(filter_name)?;
+
+        unsafe {
+            let filter = av_bsf_get_by_name(filter_name.as_ptr());
+            if filter.is_null() {
+                return Err(FFmpegError::Other(AVERROR_BSF_NOT_FOUND));
+            }
+
+            let mut ctx = ptr::null_mut();
+            check(av_bsf_alloc(filter, &mut ctx))?;
+            Ok(BsfContext(ctx))
+        }
+    }
+
+    /// Filters applied one after another, an empty list passes packets
+    /// through unchanged. Options can be given as in `-bsf`, e.g.
+    /// `"h264_metadata=level=4.1"`.
+    pub fn chain(names: &[&str]) -> Result<Self, FFmpegError> {
+        // av_bsf_list_parse_str only falls back to the null filter for a
+        // null string, an empty one is an unknown filter name
+        if names.is_empty() {
+            return BsfContext::new("null");
+        }
+
+        let names = CString::new(names.join(","))?;
+        let mut ctx = ptr::null_mut();
+
+        unsafe {
+            check(av_bsf_list_parse_str(names.as_ptr(), &mut ctx))?;
+        }
+        Ok(BsfContext(ctx))
+    }
+
+    pub fn as_ptr(&self) -> *const AVBSFContext {
+        self.0
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut AVBSFContext {
+        self.0
+    }
+
+    /// Input parameters, to be filled in before `init`.
+    pub fn par_in(&mut self) -> *mut AVCodecParameters {
+        unsafe { (*self.0).par_in }
+    }
+
+    /// Output parameters, set by `init`.
+    pub fn par_out(&self) -> *const AVCodecParameters {
+        unsafe { (*self.0).par_out }
+    }
+
+    pub fn set_time_base_in(&mut self, time_base: AVRational) {
+        unsafe {
+            (*self.0).time_base_in = time_base;
+        }
+    }
+
+    /// Set by `init`.
+    pub fn time_base_out(&self) -> AVRational {
+        unsafe { (*self.0).time_base_out }
+    }
+
+    pub fn init(&mut self) -> Result<(), FFmpegError> {
+        unsafe {
+            check(av_bsf_init(self.0))?;
+        }
+        Ok(())
+    }
+
+    /// Takes ownership of the packet's data, leaving it blank. A null `pkt`
+    /// signals the end of the stream.
+    pub unsafe fn send_packet(&mut self, pkt: *mut AVPacket) -> Result<(), FFmpegError> {
+        check(av_bsf_send_packet(self.0, pkt))?;
+        Ok(())
+    }
+
+    /// `FFmpegError::Again` means more packets have to be sent,
+    /// `FFmpegError::Eof` that no more packets will be output.
+    pub unsafe fn receive_packet(&mut self, pkt: *mut AVPacket) -> Result<(), FFmpegError> {
+        check(av_bsf_receive_packet(self.0, pkt))?;
+        Ok(())
+    }
+}
+
+impl Drop for BsfContext {
+    fn drop(&mut self) {
+        unsafe {
+            av_bsf_free(&mut self.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::slice;
+    use {av_new_packet, av_packet_alloc, av_packet_free, av_packet_unref};
+
+    unsafe fn pass_through(bsf: &mut BsfContext) {
+        bsf.set_time_base_in(AVRational { num: 1, den: 25 });
+        bsf.init().unwrap();
+
+        let mut pkt = av_packet_alloc();
+        check(av_new_packet(pkt, 4)).unwrap();
+        (*pkt).data.copy_from(b"\x01\x02\x03\x04".as_ptr(), 4);
+
+        bsf.send_packet(pkt).unwrap();
+        assert!((*pkt).data.is_null());
+        bsf.receive_packet(pkt).unwrap();
+        assert_eq!(slice::from_raw_parts((*pkt).data, 4), b"\x01\x02\x03\x04");
+        av_packet_unref(pkt);
+        assert_eq!(bsf.receive_packet(pkt), Err(FFmpegError::Again));
+
+        // an empty packet signals the end of the stream, like a null one
+        bsf.send_packet(pkt).unwrap();
+        assert_eq!(bsf.receive_packet(pkt), Err(FFmpegError::Eof));
+        av_packet_free(&mut pkt);
+    }
+
+    #[test]
+    fn null_filter() {
+        let mut bsf = BsfContext::new("null").unwrap();
+        unsafe { pass_through(&mut bsf) };
+    }
+
+    #[test]
+    fn empty_chain() {
+        let mut bsf = BsfContext::chain(&[]).unwrap();
+        unsafe { pass_through(&mut bsf) };
+    }
+
+    #[test]
+    fn unknown_filter() {
+        assert_eq!(
+            BsfContext::new("no_such_bsf").err(),
+            Some(FFmpegError::Other(AVERROR_BSF_NOT_FOUND))
+        );
+        assert!(BsfContext::chain(&["null", "no_such_bsf"]).is_err());
+    }
+}
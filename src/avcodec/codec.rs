@@ -0,0 +1,75 @@
+use std::ffi::CStr;
+use std::slice;
+
+use libc::{c_char, c_int};
+use {
+    av_codec_is_decoder, av_codec_is_encoder, AVCodec, AVPixelFormat, AVRational, AVSampleFormat,
+};
+
+// The functions below take one of libavcodec's registered codecs, whose
+// strings and capability arrays are static.
+
+unsafe fn static_str(s: *const c_char) -> &'static str {
+    if s.is_null() {
+        return "";
+    }
+
+    CStr::from_ptr(s).to_str().unwrap_or("")
+}
+
+/// Slice of a terminated array, empty if `ptr` is null.
+unsafe fn terminated<T, F: Fn(&T) -> bool>(ptr: *const T, is_end: F) -> &'static [T] {
+    if ptr.is_null() {
+        return &[];
+    }
+
+    let mut len = 0;
+    while !is_end(&*ptr.add(len)) {
+        len += 1;
+    }
+
+    slice::from_raw_parts(ptr, len)
+}
+
+pub unsafe fn codec_name(codec: *const AVCodec) -> &'static str {
+    static_str((*codec).name)
+}
+
+/// Empty if FFmpeg was built with `--enable-small`.
+pub unsafe fn codec_long_name(codec: *const AVCodec) -> &'static str {
+    static_str((*codec).long_name)
+}
+
+pub unsafe fn codec_is_encoder(codec: *const AVCodec) -> bool {
+    av_codec_is_encoder(codec) != 0
+}
+
+pub unsafe fn codec_is_decoder(codec: *const AVCodec) -> bool {
+    av_codec_is_decoder(codec) != 0
+}
+
+/// Empty if any frame rate is supported.
+pub unsafe fn codec_supported_framerates(codec: *const AVCodec) -> &'static [AVRational] {
+    terminated((*codec).supported_framerates, |rate| {
+        rate.num == 0 && rate.den == 0
+    })
+}
+
+/// Empty if unknown.
+pub unsafe fn codec_pix_fmts(codec: *const AVCodec) -> &'static [AVPixelFormat] {
+    terminated((*codec).pix_fmts, |&fmt| {
+        fmt == AVPixelFormat::AV_PIX_FMT_NONE
+    })
+}
+
+/// Empty if unknown.
+pub unsafe fn codec_sample_fmts(codec: *const AVCodec) -> &'static [AVSampleFormat] {
+    terminated((*codec).sample_fmts, |&fmt| {
+        fmt == AVSampleFormat::AV_SAMPLE_FMT_NONE
+    })
+}
+
+/// Empty if unknown.
+pub unsafe fn codec_supported_samplerates(codec: *const AVCodec) -> &'static [c_int] {
+    terminated((*codec).supported_samplerates, |&rate| rate == 0)
+}
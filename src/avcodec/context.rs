@@ -0,0 +1,142 @@
+use std::ptr;
+
+use libc::ENOMEM;
+use {
+    avcodec_alloc_context3, avcodec_free_context, avcodec_open2, avcodec_parameters_to_context,
+    avcodec_receive_frame, avcodec_send_packet, AVCodec, AVCodecContext, AVCodecParameters,
+    AVDictionary, AVFrame, AVPacket,
+};
+
+use avutil::check;
+use {FFmpegError, AVERROR};
+
+/// Owned `AVCodecContext`, freed with `avcodec_free_context` on drop.
+///
+/// The usual decoder setup is `new`, `parameters_to_context` with the
+/// stream's parameters, setting any further options, then `open`.
+pub struct CodecContext(*mut AVCodecContext);
+
+// SAFETY: AVCodecContext has no thread affinity, so it can be used from
+// whichever thread owns the CodecContext. Callbacks such as `get_buffer2`
+// and `get_format` are called from the thread decoding (or from libavcodec's
+// worker threads) with the context's `opaque` pointer; moving the context to
+// another thread relies on the callbacks and opaque data set through
+// `as_mut_ptr` being thread-safe. See the safety_notes module.
+unsafe impl Send for CodecContext {}
+
+impl CodecContext {
+    /// `codec` may be null, in which case it has to be passed to `open`.
+    pub unsafe fn new(codec: *const AVCodec) -> Result<Self, FFmpegError> {
+        let ctx = avcodec_alloc_context3(codec);
+        if ctx.is_null() {
+            return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+        }
+
+        Ok(CodecContext(ctx))
+    }
+
+    pub fn as_ptr(&self) -> *const AVCodecContext {
+        self.0
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut AVCodecContext {
+        self.0
+    }
+
+    pub unsafe fn parameters_to_context(
+        &mut self,
+        par: *const AVCodecParameters,
+    ) -> Result<(), FFmpegError> {
+        check(avcodec_parameters_to_context(self.0, par))?;
+        Ok(())
+    }
+
+    /// Options consumed by the codec are removed from `options`, the
+    /// remaining ones are left for the caller to report.
+    pub unsafe fn open(
+        &mut self,
+        codec: *const AVCodec,
+        options: Option<*mut *mut AVDictionary>,
+    ) -> Result<(), FFmpegError> {
+        check(avcodec_open2(
+            self.0,
+            codec,
+            options.unwrap_or(ptr::null_mut()),
+        ))?;
+        Ok(())
+    }
+
+    /// `FFmpegError::Again` means frames have to be received before more
+    /// packets are accepted. A null `pkt` starts draining the decoder.
+    pub unsafe fn send_packet(&mut self, pkt: *const AVPacket) -> Result<(), FFmpegError> {
+        check(avcodec_send_packet(self.0, pkt))?;
+        Ok(())
+    }
+
+    /// `FFmpegError::Again` means more packets are needed, `FFmpegError::Eof`
+    /// that the decoder has been fully drained.
+    pub unsafe fn receive_frame(&mut self, frame: *mut AVFrame) -> Result<(), FFmpegError> {
+        check(avcodec_receive_frame(self.0, frame))?;
+        Ok(())
+    }
+}
+
+impl Drop for CodecContext {
+    fn drop(&mut self) {
+        unsafe {
+            avcodec_free_context(&mut self.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::register_all;
+    use std::slice;
+    use {
+        av_frame_alloc, av_frame_free, av_new_packet, av_packet_alloc, av_packet_free,
+        avcodec_find_decoder, AVCodecID, AVPixelFormat,
+    };
+
+    #[test]
+    fn decode_rawvideo() {
+        register_all();
+        let image: Vec<u8> = (0..8).collect();
+
+        unsafe {
+            let codec = avcodec_find_decoder(AVCodecID::AV_CODEC_ID_RAWVIDEO);
+            assert!(!codec.is_null());
+
+            let mut ctx = CodecContext::new(codec).unwrap();
+            {
+                let ctx = &mut *ctx.as_mut_ptr();
+                ctx.width = 4;
+                ctx.height = 2;
+                ctx.pix_fmt = AVPixelFormat::AV_PIX_FMT_GRAY8;
+            }
+            ctx.open(codec, None).unwrap();
+
+            let mut pkt = av_packet_alloc();
+            check(av_new_packet(pkt, image.len() as _)).unwrap();
+            ptr::copy_nonoverlapping(image.as_ptr(), (*pkt).data, image.len());
+            let mut frame = av_frame_alloc();
+
+            ctx.send_packet(pkt).unwrap();
+            ctx.receive_frame(frame).unwrap();
+            assert_eq!(((*frame).width, (*frame).height), (4, 2));
+            assert_eq!((*frame).format, AVPixelFormat::AV_PIX_FMT_GRAY8 as _);
+            for y in 0..2 {
+                let row = (*frame).data[0].offset(y * (*frame).linesize[0] as isize);
+                assert_eq!(slice::from_raw_parts(row, 4), &image[y as usize * 4..][..4]);
+            }
+            assert_eq!(ctx.receive_frame(frame), Err(FFmpegError::Again));
+
+            ctx.send_packet(ptr::null()).unwrap();
+            assert_eq!(ctx.receive_frame(frame), Err(FFmpegError::Eof));
+
+            av_frame_free(&mut frame);
+            av_packet_free(&mut pkt);
+        }
+    }
+}
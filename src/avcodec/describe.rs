@@ -0,0 +1,70 @@
+use std::ffi::CStr;
+
+use libc::{c_char, c_int};
+use {avcodec_get_name, avcodec_string, AVCodecContext, AVCodecID};
+
+#[cfg(not(feature = "ffmpeg_4_1"))]
+use av_get_codec_tag_string;
+#[cfg(feature = "ffmpeg_4_1")]
+use {av_fourcc_make_string, AV_FOURCC_MAX_STRING_SIZE};
+
+/// One-line summary of a codec context, as printed by `av_dump_format`.
+pub unsafe fn codec_context_description(ctx: *const AVCodecContext, encode: bool) -> String {
+    let mut buf = [0 as c_char; 256];
+    avcodec_string(
+        buf.as_mut_ptr(),
+        buf.len() as c_int,
+        ctx as *mut _,
+        encode as c_int,
+    );
+
+    CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+}
+
+/// `"none"` for `AV_CODEC_ID_NONE`, `"unknown_codec"` for IDs this build of
+/// libavcodec does not know about.
+pub fn codec_id_name(id: AVCodecID) -> &'static str {
+    unsafe {
+        let name = avcodec_get_name(id);
+        if name.is_null() {
+            return "unknown_codec";
+        }
+
+        CStr::from_ptr(name).to_str().unwrap_or("unknown_codec")
+    }
+}
+
+/// Printable form of a codec tag, with non-printable bytes written as
+/// `[<decimal value>]`.
+pub fn codec_tag_string(tag: u32) -> String {
+    #[cfg(feature = "ffmpeg_4_1")]
+    unsafe {
+        let mut buf = [0 as c_char; AV_FOURCC_MAX_STRING_SIZE as usize];
+        av_fourcc_make_string(buf.as_mut_ptr(), tag);
+        CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+    }
+
+    #[cfg(not(feature = "ffmpeg_4_1"))]
+    unsafe {
+        let mut buf = [0 as c_char; 32];
+        av_get_codec_tag_string(buf.as_mut_ptr(), buf.len(), tag as _);
+        CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_names() {
+        assert_eq!(codec_id_name(AVCodecID::AV_CODEC_ID_H264), "h264");
+        assert_eq!(codec_id_name(AVCodecID::AV_CODEC_ID_NONE), "none");
+    }
+
+    #[test]
+    fn tag_strings() {
+        assert_eq!(codec_tag_string(u32::from_le_bytes(*b"avc1")), "avc1");
+        assert_eq!(codec_tag_string(u32::from_le_bytes(*b"ab\x01c")), "ab[1]c");
+    }
+}
@@ -0,0 +1,94 @@
+use libc::{c_int, c_uint};
+use {
+    av_dv_codec_profile, av_dv_codec_profile2, av_dv_frame_profile, AVDVProfile, AVPixelFormat,
+    AVRational,
+};
+
+/// DV profile matching the given dimensions and pixel format. Profiles are
+/// static tables inside libavcodec and are never freed.
+pub fn dv_codec_profile(
+    width: c_int,
+    height: c_int,
+    pix_fmt: AVPixelFormat,
+) -> Option<*const AVDVProfile> {
+    let profile = unsafe { av_dv_codec_profile(width, height, pix_fmt) };
+    if profile.is_null() {
+        None
+    } else {
+        Some(profile)
+    }
+}
+
+/// Like `dv_codec_profile`, additionally matching on the frame rate.
+pub fn dv_codec_profile2(
+    width: c_int,
+    height: c_int,
+    pix_fmt: AVPixelFormat,
+    frame_rate: AVRational,
+) -> Option<*const AVDVProfile> {
+    let profile = unsafe { av_dv_codec_profile2(width, height, pix_fmt, frame_rate) };
+    if profile.is_null() {
+        None
+    } else {
+        Some(profile)
+    }
+}
+
+/// DV profile of a compressed frame of `buf_size` bytes, using `sys` (which
+/// may be null) as a hint.
+pub unsafe fn dv_frame_profile(
+    sys: *const AVDVProfile,
+    frame: *const u8,
+    buf_size: c_uint,
+) -> Option<*const AVDVProfile> {
+    let profile = av_dv_frame_profile(sys, frame, buf_size);
+    if profile.is_null() {
+        None
+    } else {
+        Some(profile)
+    }
+}
+
+/// Reference to one of libavcodec's static `AVDVProfile` tables.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DVProfile(*const AVDVProfile);
+
+// SAFETY: DV profiles are immutable static data; see the safety_notes module.
+unsafe impl Send for DVProfile {}
+unsafe impl Sync for DVProfile {}
+
+impl DVProfile {
+    /// `ptr` must be null or one of the profiles returned by libavcodec.
+    pub unsafe fn from_ptr(ptr: *const AVDVProfile) -> Option<Self> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(DVProfile(ptr))
+        }
+    }
+
+    pub fn as_ptr(&self) -> *const AVDVProfile {
+        self.0
+    }
+
+    pub fn width(&self) -> c_int {
+        unsafe { (*self.0).width }
+    }
+
+    pub fn height(&self) -> c_int {
+        unsafe { (*self.0).height }
+    }
+
+    pub fn pix_fmt(&self) -> AVPixelFormat {
+        unsafe { (*self.0).pix_fmt }
+    }
+
+    pub fn time_base(&self) -> AVRational {
+        unsafe { (*self.0).time_base }
+    }
+
+    /// Total size of one frame in bytes.
+    pub fn frame_size(&self) -> c_int {
+        unsafe { (*self.0).frame_size }
+    }
+}
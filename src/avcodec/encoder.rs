@@ -0,0 +1,153 @@
+use std::ptr;
+
+use {avcodec_receive_packet, avcodec_send_frame, AVCodecContext, AVFrame, AVPacket};
+
+use avutil::check;
+use FFmpegError;
+
+/// `FFmpegError::Again` means the encoder's buffer is full and packets have
+/// to be received before more frames are accepted. A null `frame` starts
+/// draining the encoder, see `flush_encoder`.
+pub unsafe fn send_frame(
+    ctx: *mut AVCodecContext,
+    frame: *const AVFrame,
+) -> Result<(), FFmpegError> {
+    check(avcodec_send_frame(ctx, frame))?;
+    Ok(())
+}
+
+/// `FFmpegError::Again` means more frames are needed before the next packet,
+/// `FFmpegError::Eof` that the encoder has been fully drained.
+pub unsafe fn receive_packet(
+    ctx: *mut AVCodecContext,
+    pkt: *mut AVPacket,
+) -> Result<(), FFmpegError> {
+    check(avcodec_receive_packet(ctx, pkt))?;
+    Ok(())
+}
+
+/// Iterator draining an encoder, see `flush_encoder`.
+pub struct FlushEncoder {
+    ctx: *mut AVCodecContext,
+    pkt: *mut AVPacket,
+    flushing: bool,
+    done: bool,
+}
+
+impl Iterator for FlushEncoder {
+    type Item = Result<(), FFmpegError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if !self.flushing {
+            self.flushing = true;
+            match unsafe { send_frame(self.ctx, ptr::null()) } {
+                // already draining
+                Ok(()) | Err(FFmpegError::Eof) => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        match unsafe { receive_packet(self.ctx, self.pkt) } {
+            Ok(()) => Some(Ok(())),
+            Err(FFmpegError::Eof) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Sends the end of stream to the encoder and receives its remaining
+/// packets into `pkt`, one per `Ok(())` yielded.
+///
+/// Each packet has to be consumed (and unreferenced) before advancing the
+/// iterator. `ctx` and `pkt` must stay valid while it is alive. The
+/// iterator stops after the first error.
+pub unsafe fn flush_encoder(ctx: *mut AVCodecContext, pkt: *mut AVPacket) -> FlushEncoder {
+    FlushEncoder {
+        ctx,
+        pkt,
+        flushing: false,
+        done: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::register_all;
+    use std::slice;
+    use {
+        av_frame_alloc, av_frame_free, av_frame_get_buffer, av_packet_alloc, av_packet_free,
+        av_packet_unref, avcodec_alloc_context3, avcodec_find_encoder, avcodec_free_context,
+        avcodec_open2, AVCodecID, AVSampleFormat,
+    };
+
+    #[cfg(feature = "ffmpeg_5_1")]
+    use av_channel_layout_default;
+
+    #[test]
+    fn encode_pcm_silence() {
+        register_all();
+
+        unsafe {
+            let codec = avcodec_find_encoder(AVCodecID::AV_CODEC_ID_PCM_S16LE);
+            assert!(!codec.is_null());
+
+            let mut ctx = avcodec_alloc_context3(codec);
+            (*ctx).sample_fmt = AVSampleFormat::AV_SAMPLE_FMT_S16;
+            (*ctx).sample_rate = 8000;
+            #[cfg(feature = "ffmpeg_5_1")]
+            av_channel_layout_default(&mut (*ctx).ch_layout, 1);
+            #[cfg(not(feature = "ffmpeg_5_1"))]
+            {
+                (*ctx).channels = 1;
+            }
+            check(avcodec_open2(ctx, codec, ptr::null_mut())).unwrap();
+
+            let mut frame = av_frame_alloc();
+            (*frame).format = AVSampleFormat::AV_SAMPLE_FMT_S16 as _;
+            (*frame).nb_samples = 100;
+            #[cfg(feature = "ffmpeg_5_1")]
+            av_channel_layout_default(&mut (*frame).ch_layout, 1);
+            #[cfg(not(feature = "ffmpeg_5_1"))]
+            {
+                (*frame).channels = 1;
+            }
+            check(av_frame_get_buffer(frame, 0)).unwrap();
+            ptr::write_bytes((*frame).data[0], 0, 200);
+
+            let mut pkt = av_packet_alloc();
+            for _ in 0..3 {
+                send_frame(ctx, frame).unwrap();
+                receive_packet(ctx, pkt).unwrap();
+                assert!(slice::from_raw_parts((*pkt).data, (*pkt).size as usize)
+                    .iter()
+                    .all(|&b| b == 0));
+                assert_eq!((*pkt).size, 200);
+                av_packet_unref(pkt);
+                assert_eq!(receive_packet(ctx, pkt), Err(FFmpegError::Again));
+            }
+
+            // PCM encoders don't buffer anything
+            assert_eq!(flush_encoder(ctx, pkt).count(), 0);
+            assert_eq!(receive_packet(ctx, pkt), Err(FFmpegError::Eof));
+            assert_eq!(flush_encoder(ctx, pkt).count(), 0);
+
+            av_packet_free(&mut pkt);
+            av_frame_free(&mut frame);
+            avcodec_free_context(&mut ctx);
+        }
+    }
+}
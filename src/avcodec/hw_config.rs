@@ -0,0 +1,64 @@
+use libc::c_int;
+use {avcodec_get_hw_config, AVCodec, AVCodecHWConfig, AVHWDeviceType};
+
+/// Iterator over the hardware configurations supported by a codec, as
+/// returned by `avcodec_get_hw_config`.
+pub struct HwConfigs {
+    codec: *const AVCodec,
+    index: c_int,
+}
+
+impl Iterator for HwConfigs {
+    type Item = &'static AVCodecHWConfig;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            let config = avcodec_get_hw_config(self.codec, self.index);
+            if config.is_null() {
+                return None;
+            }
+
+            self.index += 1;
+            Some(&*config)
+        }
+    }
+}
+
+/// `codec` must be one of libavcodec's registered codecs, whose hardware
+/// configurations are static.
+pub unsafe fn hw_configs(codec: *const AVCodec) -> HwConfigs {
+    HwConfigs { codec, index: 0 }
+}
+
+/// First configuration of `codec` usable with devices of type `typ`.
+pub unsafe fn hw_config_for_device_type(
+    codec: *const AVCodec,
+    typ: AVHWDeviceType,
+) -> Option<&'static AVCodecHWConfig> {
+    hw_configs(codec).find(|config| config.device_type == typ)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {avcodec_find_decoder, AVCodecID};
+
+    #[test]
+    fn h264_decoder_configs() {
+        let codec = unsafe { avcodec_find_decoder(AVCodecID::AV_CODEC_ID_H264) };
+        if codec.is_null() {
+            return;
+        }
+
+        // which configs exist depends on the hwaccels FFmpeg was built with,
+        // so only check that the lookups agree with each other
+        unsafe {
+            for config in hw_configs(codec) {
+                assert_ne!(config.methods, 0);
+
+                let found = hw_config_for_device_type(codec, config.device_type).unwrap();
+                assert_eq!(found.device_type, config.device_type);
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+mod dv_profile;
+pub use self::dv_profile::*;
+
+#[cfg(feature = "ffmpeg_4_0")]
+mod hw_config;
+#[cfg(feature = "ffmpeg_4_0")]
+pub use self::hw_config::*;
+
+mod codec;
+pub use self::codec::*;
+
+mod describe;
+pub use self::describe::*;
+
+mod subtitle;
+pub use self::subtitle::*;
+
+mod context;
+pub use self::context::*;
+
+mod bsf;
+pub use self::bsf::*;
+
+mod parser;
+pub use self::parser::*;
+
+mod encoder;
+pub use self::encoder::*;
+
+mod audio_frame;
+pub use self::audio_frame::*;
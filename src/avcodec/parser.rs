@@ -0,0 +1,132 @@
+use std::ptr;
+#[cfg(feature = "ffmpeg_4_0")]
+use std::vec;
+
+#[cfg(feature = "ffmpeg_4_0")]
+use libc::c_void;
+use libc::{c_int, EINVAL};
+use {av_parser_close, av_parser_init, av_parser_parse2, AVCodecContext, AVCodecParserContext};
+
+#[cfg(feature = "ffmpeg_4_0")]
+use av_parser_iterate;
+
+use avutil::check;
+use {FFmpegError, AVERROR};
+
+/// Owned codec parser, closed with `av_parser_close` on drop.
+pub struct ParserContext(*mut AVCodecParserContext);
+
+// SAFETY: AVCodecParserContext has no thread affinity and is only accessed
+// through ParserContext, which requires &mut self for every operation
+// mutating it; see the safety_notes module.
+unsafe impl Send for ParserContext {}
+
+impl ParserContext {
+    /// Fails with `EINVAL` if there is no parser for `codec_id`.
+    pub fn new(codec_id: c_int) -> Result<Self, FFmpegError> {
+        let parser = unsafe { av_parser_init(codec_id) };
+        if parser.is_null() {
+            return Err(FFmpegError::Other(AVERROR(EINVAL)));
+        }
+
+        Ok(ParserContext(parser))
+    }
+
+    pub fn as_ptr(&self) -> *const AVCodecParserContext {
+        self.0
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut AVCodecParserContext {
+        self.0
+    }
+
+    /// Feeds `buf` to the parser and returns the number of bytes consumed.
+    ///
+    /// When a complete packet is available, `pkt_data` and `pkt_size` are set
+    /// to it, otherwise `pkt_size` is 0. The packet data is owned by the
+    /// parser (or is a part of `buf`) and only valid until the next call. An
+    /// empty `buf` flushes the last packet at the end of the stream. Fails with
+    /// `EINVAL` if `buf` is larger than `c_int::MAX` bytes.
+    pub unsafe fn parse(
+        &mut self,
+        avctx: *mut AVCodecContext,
+        pkt_data: &mut *const u8,
+        pkt_size: &mut c_int,
+        buf: &[u8],
+        pts: i64,
+        dts: i64,
+        pos: i64,
+    ) -> Result<c_int, FFmpegError> {
+        if buf.len() > c_int::MAX as usize {
+            return Err(FFmpegError::Other(AVERROR(EINVAL)));
+        }
+
+        check(av_parser_parse2(
+            self.0,
+            avctx,
+            pkt_data as *mut *const u8 as *mut *mut u8,
+            pkt_size,
+            if buf.is_empty() {
+                ptr::null()
+            } else {
+                buf.as_ptr()
+            },
+            buf.len() as c_int,
+            pts,
+            dts,
+            pos,
+        ))
+    }
+}
+
+impl Drop for ParserContext {
+    fn drop(&mut self) {
+        unsafe {
+            av_parser_close(self.0);
+        }
+    }
+}
+
+/// Iterator over the codec IDs for which a parser is available.
+#[cfg(feature = "ffmpeg_4_0")]
+pub struct ParserCodecs {
+    opaque: *mut c_void,
+    pending: vec::IntoIter<c_int>,
+}
+
+#[cfg(feature = "ffmpeg_4_0")]
+impl Iterator for ParserCodecs {
+    type Item = c_int;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(id) = self.pending.next() {
+                return Some(id);
+            }
+
+            unsafe {
+                let parser = av_parser_iterate(&mut self.opaque);
+                if parser.is_null() {
+                    return None;
+                }
+
+                // unused slots are AV_CODEC_ID_NONE
+                self.pending = (*parser)
+                    .codec_ids
+                    .iter()
+                    .cloned()
+                    .filter(|&id| id != 0)
+                    .collect::<Vec<_>>()
+                    .into_iter();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ffmpeg_4_0")]
+pub fn codecs_with_parsers() -> ParserCodecs {
+    ParserCodecs {
+        opaque: ptr::null_mut(),
+        pending: Vec::new().into_iter(),
+    }
+}
@@ -0,0 +1,216 @@
+use std::ffi::CStr;
+use std::marker::PhantomData;
+use std::mem;
+use std::slice;
+
+use libc::{c_char, c_int};
+use {
+    avcodec_decode_subtitle2, avsubtitle_free, AVCodecContext, AVPacket, AVSubtitle,
+    AVSubtitleRect, AVSubtitleType,
+};
+
+use avutil::check;
+use FFmpegError;
+
+/// Owned `AVSubtitle`, released with `avsubtitle_free` on drop.
+pub struct Subtitle(AVSubtitle);
+
+// SAFETY: the rects are owned by the subtitle and only read through &self;
+// see the safety_notes module.
+unsafe impl Send for Subtitle {}
+unsafe impl Sync for Subtitle {}
+
+impl Subtitle {
+    pub fn new() -> Self {
+        Subtitle(unsafe { mem::zeroed() })
+    }
+
+    pub fn as_ptr(&self) -> *const AVSubtitle {
+        &self.0
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut AVSubtitle {
+        &mut self.0
+    }
+
+    /// In `AV_TIME_BASE` units.
+    pub fn pts(&self) -> i64 {
+        self.0.pts
+    }
+
+    /// Relative to `pts`, in milliseconds.
+    pub fn start_display_time(&self) -> u32 {
+        self.0.start_display_time
+    }
+
+    /// Relative to `pts`, in milliseconds.
+    pub fn end_display_time(&self) -> u32 {
+        self.0.end_display_time
+    }
+
+    pub fn rects(&self) -> SubtitleRects<'_> {
+        if self.0.rects.is_null() {
+            return SubtitleRects([].iter(), PhantomData);
+        }
+
+        unsafe {
+            SubtitleRects(
+                slice::from_raw_parts(self.0.rects, self.0.num_rects as usize).iter(),
+                PhantomData,
+            )
+        }
+    }
+}
+
+impl Default for Subtitle {
+    fn default() -> Self {
+        Subtitle::new()
+    }
+}
+
+impl Drop for Subtitle {
+    fn drop(&mut self) {
+        unsafe {
+            avsubtitle_free(&mut self.0);
+        }
+    }
+}
+
+/// Borrowed `AVSubtitleRect` of a `Subtitle`.
+#[derive(Debug, Copy, Clone)]
+pub struct SubtitleRect<'a>(*const AVSubtitleRect, PhantomData<&'a Subtitle>);
+
+unsafe fn optional_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+
+    CStr::from_ptr(s).to_str().ok()
+}
+
+impl<'a> SubtitleRect<'a> {
+    pub fn as_ptr(&self) -> *const AVSubtitleRect {
+        self.0
+    }
+
+    pub fn type_(&self) -> AVSubtitleType {
+        unsafe { (*self.0).type_ }
+    }
+
+    /// Plain text, for `SUBTITLE_TEXT` rects.
+    pub fn text(&self) -> Option<&'a str> {
+        unsafe { optional_str((*self.0).text) }
+    }
+
+    /// ASS dialogue line, for `SUBTITLE_ASS` rects.
+    pub fn ass(&self) -> Option<&'a str> {
+        unsafe { optional_str((*self.0).ass) }
+    }
+
+    // The geometry is only meaningful for SUBTITLE_BITMAP rects.
+
+    pub fn x(&self) -> c_int {
+        unsafe { (*self.0).x }
+    }
+
+    pub fn y(&self) -> c_int {
+        unsafe { (*self.0).y }
+    }
+
+    pub fn w(&self) -> c_int {
+        unsafe { (*self.0).w }
+    }
+
+    pub fn h(&self) -> c_int {
+        unsafe { (*self.0).h }
+    }
+}
+
+/// Iterator over the rects of a `Subtitle`.
+pub struct SubtitleRects<'a>(
+    slice::Iter<'a, *mut AVSubtitleRect>,
+    PhantomData<&'a Subtitle>,
+);
+
+impl<'a> Iterator for SubtitleRects<'a> {
+    type Item = SubtitleRect<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|&rect| SubtitleRect(rect, PhantomData))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for SubtitleRects<'a> {}
+
+/// Decodes `pkt` into `sub`, replacing its previous contents. `got_sub` is
+/// set to non-zero if a subtitle was produced; the number of bytes consumed
+/// is returned.
+pub unsafe fn decode_subtitle(
+    ctx: *mut AVCodecContext,
+    sub: &mut Subtitle,
+    got_sub: &mut c_int,
+    pkt: *const AVPacket,
+) -> Result<c_int, FFmpegError> {
+    // avcodec_decode_subtitle2 overwrites the struct without freeing it
+    avsubtitle_free(&mut sub.0);
+
+    // avpkt is only const since FFmpeg 6.0
+    check(avcodec_decode_subtitle2(
+        ctx,
+        &mut sub.0,
+        got_sub,
+        pkt as *mut _,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::register_all;
+    use std::ptr;
+    use {
+        av_new_packet, av_packet_alloc, av_packet_free, avcodec_alloc_context3,
+        avcodec_find_decoder, avcodec_free_context, avcodec_open2, AVCodecID,
+    };
+
+    #[test]
+    fn decode_ass() {
+        register_all();
+        let event = "0,0,Default,,0,0,0,,Hello, world!";
+
+        unsafe {
+            let codec = avcodec_find_decoder(AVCodecID::AV_CODEC_ID_ASS);
+            if codec.is_null() {
+                return;
+            }
+            let mut ctx = avcodec_alloc_context3(codec);
+            check(avcodec_open2(ctx, codec, ptr::null_mut())).unwrap();
+
+            // av_new_packet zeroes the padding, which NUL-terminates the event
+            let mut pkt = av_packet_alloc();
+            check(av_new_packet(pkt, event.len() as c_int)).unwrap();
+            ptr::copy_nonoverlapping(event.as_ptr(), (*pkt).data, event.len());
+
+            let mut sub = Subtitle::new();
+            let mut got_sub = 0;
+            let consumed = decode_subtitle(ctx, &mut sub, &mut got_sub, pkt).unwrap();
+            assert_eq!(consumed, event.len() as c_int);
+            assert_ne!(got_sub, 0);
+
+            let rects: Vec<_> = sub.rects().collect();
+            assert_eq!(rects.len(), 1);
+            assert_eq!(rects[0].type_(), AVSubtitleType::SUBTITLE_ASS);
+            assert_eq!(rects[0].text(), None);
+            // before FFmpeg 4.0 the event is rewritten as a full "Dialogue:"
+            // line with timings by default
+            assert!(rects[0].ass().unwrap().contains(event));
+
+            av_packet_free(&mut pkt);
+            avcodec_free_context(&mut ctx);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use libc::{EINVAL, ENOMEM};
+use {av_buffersink_get_frame, av_frame_alloc, av_frame_free, AVFilterContext, AVFrame};
+
+use avutil::check;
+use {FFmpegError, AVERROR};
+
+/// Pulls the next filtered frame out of a `buffersink`/`abuffersink` filter.
+///
+/// The returned frame is newly allocated and must be released with
+/// `av_frame_free`. `FFmpegError::Again` means more input has to be pushed
+/// into the graph before a frame is available, `FFmpegError::Eof` that the
+/// sink has been drained.
+pub unsafe fn pull_frame(ctx: *mut AVFilterContext) -> Result<*mut AVFrame, FFmpegError> {
+    if ctx.is_null() {
+        return Err(FFmpegError::Other(AVERROR(EINVAL)));
+    }
+
+    let mut frame = av_frame_alloc();
+    if frame.is_null() {
+        return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+    }
+
+    match check(av_buffersink_get_frame(ctx, frame)) {
+        Ok(_) => Ok(frame),
+        Err(e) => {
+            av_frame_free(&mut frame);
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::ptr;
+
+use libc::{c_int, EINVAL};
+use {av_buffersrc_add_frame_flags, AVFilterContext, AVFrame};
+
+use avutil::check;
+use {FFmpegError, AVERROR};
+
+/// Pushes `frame` into a `buffer`/`abuffer` source filter.
+///
+/// `flags` is a combination of `AV_BUFFERSRC_FLAG_*`; unless
+/// `AV_BUFFERSRC_FLAG_KEEP_REF` is set, the frame's references are moved into
+/// the filter and the frame is left blank.
+pub unsafe fn push_frame(
+    ctx: *mut AVFilterContext,
+    frame: *mut AVFrame,
+    flags: c_int,
+) -> Result<(), FFmpegError> {
+    if ctx.is_null() || frame.is_null() {
+        return Err(FFmpegError::Other(AVERROR(EINVAL)));
+    }
+
+    check(av_buffersrc_add_frame_flags(ctx, frame, flags)).map(|_| ())
+}
+
+/// Marks the end of the stream on a buffer source filter.
+pub unsafe fn push_eof(ctx: *mut AVFilterContext) -> Result<(), FFmpegError> {
+    if ctx.is_null() {
+        return Err(FFmpegError::Other(AVERROR(EINVAL)));
+    }
+
+    check(av_buffersrc_add_frame_flags(ctx, ptr::null_mut(), 0)).map(|_| ())
+}
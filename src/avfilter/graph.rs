@@ -0,0 +1,184 @@
+use std::ffi::CString;
+use std::ptr;
+
+use libc::{c_void, ENOMEM};
+use {
+    avfilter_graph_alloc, avfilter_graph_config, avfilter_graph_create_filter, avfilter_graph_free,
+    avfilter_graph_parse_ptr, AVFilter, AVFilterContext, AVFilterGraph, AVFilterInOut,
+};
+
+use avutil::check;
+use {FFmpegError, AVERROR};
+
+/// Owned `AVFilterGraph`, freed along with all of its filters by
+/// `avfilter_graph_free` on drop.
+pub struct FilterGraph(*mut AVFilterGraph);
+
+// SAFETY: AVFilterGraph has no thread affinity and is only accessed through
+// FilterGraph, which requires &mut self for every operation mutating it;
+// see the safety_notes module.
+unsafe impl Send for FilterGraph {}
+
+impl FilterGraph {
+    pub fn new() -> Result<Self, FFmpegError> {
+        let graph = unsafe { avfilter_graph_alloc() };
+        if graph.is_null() {
+            return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+        }
+
+        Ok(FilterGraph(graph))
+    }
+
+    pub fn as_ptr(&self) -> *const AVFilterGraph {
+        self.0
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut AVFilterGraph {
+        self.0
+    }
+
+    /// Creates and initializes an instance of `filt` named `name`. The
+    /// returned context is owned by the graph.
+    pub unsafe fn create_filter(
+        &mut self,
+        filt: *const AVFilter,
+        name: &str,
+        args: Option<&str>,
+        opaque: *mut c_void,
+    ) -> Result<*mut AVFilterContext, FFmpegError> {
+        let name = CString::new(name)?;
+        let args = match args {
+            Some(args) => Some(CString::new(args)?),
+            None => None,
+        };
+        let mut ctx = ptr::null_mut();
+
+        check(avfilter_graph_create_filter(
+            &mut ctx,
+            filt,
+            name.as_ptr(),
+            args.as_ref().map_or(ptr::null(), |args| args.as_ptr()),
+            opaque,
+            self.0,
+        ))?;
+        Ok(ctx)
+    }
+
+    /// Adds the filters described by `filters` (see `FilterGraphBuilder`),
+    /// linking its open pads to `inputs` and `outputs`. The lists are updated
+    /// to the pads left unlinked and have to be freed with
+    /// `avfilter_inout_free`.
+    pub unsafe fn parse_ptr(
+        &mut self,
+        filters: &str,
+        inputs: *mut *mut AVFilterInOut,
+        outputs: *mut *mut AVFilterInOut,
+        log_ctx: *mut c_void,
+    ) -> Result<(), FFmpegError> {
+        let filters = CString::new(filters)?;
+        check(avfilter_graph_parse_ptr(
+            self.0,
+            filters.as_ptr(),
+            inputs,
+            outputs,
+            log_ctx,
+        ))?;
+        Ok(())
+    }
+
+    /// Checks the links and negotiates formats; errors are logged against
+    /// `log_ctx`, which may be null.
+    pub unsafe fn config(&mut self, log_ctx: *mut c_void) -> Result<(), FFmpegError> {
+        check(avfilter_graph_config(self.0, log_ctx))?;
+        Ok(())
+    }
+}
+
+impl Drop for FilterGraph {
+    fn drop(&mut self) {
+        unsafe {
+            avfilter_graph_free(&mut self.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::register_all;
+    use std::ffi::CStr;
+    use {
+        av_buffersink_get_frame, av_buffersrc_add_frame_flags, av_frame_alloc, av_frame_free,
+        av_frame_get_buffer, av_strdup, avfilter_get_by_name, avfilter_inout_alloc,
+        avfilter_inout_free, AVPixelFormat,
+    };
+
+    unsafe fn filter(name: &[u8]) -> *const AVFilter {
+        let filt = avfilter_get_by_name(CStr::from_bytes_with_nul(name).unwrap().as_ptr());
+        assert!(!filt.is_null());
+        filt
+    }
+
+    unsafe fn inout(name: &[u8], filter_ctx: *mut AVFilterContext) -> *mut AVFilterInOut {
+        let inout = avfilter_inout_alloc();
+        (*inout).name = av_strdup(CStr::from_bytes_with_nul(name).unwrap().as_ptr());
+        (*inout).filter_ctx = filter_ctx;
+        (*inout).pad_idx = 0;
+        (*inout).next = ptr::null_mut();
+        inout
+    }
+
+    #[test]
+    fn scale() {
+        register_all();
+
+        unsafe {
+            let mut graph = FilterGraph::new().unwrap();
+            let src = graph
+                .create_filter(
+                    filter(b"buffer\0"),
+                    "in",
+                    Some("video_size=640x480:pix_fmt=yuv420p:time_base=1/25:pixel_aspect=1/1"),
+                    ptr::null_mut(),
+                )
+                .unwrap();
+            let sink = graph
+                .create_filter(filter(b"buffersink\0"), "out", None, ptr::null_mut())
+                .unwrap();
+
+            // the description's open input is fed by the source, its open
+            // output feeds the sink
+            let mut outputs = inout(b"in\0", src);
+            let mut inputs = inout(b"out\0", sink);
+            let ret = graph.parse_ptr("scale=320:240", &mut inputs, &mut outputs, ptr::null_mut());
+            avfilter_inout_free(&mut inputs);
+            avfilter_inout_free(&mut outputs);
+            ret.unwrap();
+            graph.config(ptr::null_mut()).unwrap();
+
+            let mut frame = av_frame_alloc();
+            (*frame).width = 640;
+            (*frame).height = 480;
+            (*frame).format = AVPixelFormat::AV_PIX_FMT_YUV420P as _;
+            check(av_frame_get_buffer(frame, 32)).unwrap();
+            check(av_buffersrc_add_frame_flags(src, frame, 0)).unwrap();
+            check(av_buffersink_get_frame(sink, frame)).unwrap();
+            assert_eq!(((*frame).width, (*frame).height), (320, 240));
+            av_frame_free(&mut frame);
+        }
+    }
+
+    #[test]
+    fn unknown_filter() {
+        register_all();
+
+        unsafe {
+            let mut graph = FilterGraph::new().unwrap();
+            let mut inputs = ptr::null_mut();
+            let mut outputs = ptr::null_mut();
+            assert!(graph
+                .parse_ptr("no_such_filter", &mut inputs, &mut outputs, ptr::null_mut())
+                .is_err());
+        }
+    }
+}
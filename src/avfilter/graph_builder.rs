@@ -0,0 +1,153 @@
+/// A filter added to a `FilterGraphBuilder`, with the pad labels attached to
+/// it in order of appearance (inputs first, then outputs).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilterContext {
+    pub name: String,
+    pub pad_labels: Vec<String>,
+}
+
+/// Builds filtergraph descriptions as accepted by `avfilter_graph_parse_ptr`,
+/// e.g. `"[0:v]scale=1280:720[v];[v][1:v]overlay=0:0[out]"`.
+///
+/// Option values are escaped for both the option and the filtergraph parser,
+/// so they may contain any of the separator characters.
+#[derive(Clone, Debug, Default)]
+pub struct FilterGraphBuilder {
+    description: String,
+    filters: Vec<FilterContext>,
+    pending_labels: Vec<String>,
+}
+
+impl FilterGraphBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `name=key=val:key2=val2`. Options with an empty key are
+    /// written as positional values.
+    pub fn add_filter(&mut self, name: &str, options: &[(&str, &str)]) -> &mut Self {
+        self.description.push_str(name);
+
+        for (i, &(key, value)) in options.iter().enumerate() {
+            self.description.push(if i == 0 { '=' } else { ':' });
+            if !key.is_empty() {
+                self.description.push_str(key);
+                self.description.push('=');
+            }
+            self.description.push_str(&escape_value(value));
+        }
+
+        self.filters.push(FilterContext {
+            name: name.to_string(),
+            pad_labels: self.pending_labels.drain(..).collect(),
+        });
+        self
+    }
+
+    /// Starts a new chain (`;`).
+    pub fn chain(&mut self) -> &mut Self {
+        self.description.push(';');
+        self
+    }
+
+    /// Links the previous filter to the next one (`,`).
+    pub fn link(&mut self) -> &mut Self {
+        self.description.push(',');
+        self
+    }
+
+    /// Labels an input pad of the next filter.
+    pub fn named_input(&mut self, label: &str) -> &mut Self {
+        self.push_label(label);
+        self.pending_labels.push(label.to_string());
+        self
+    }
+
+    /// Labels an output pad of the last filter.
+    pub fn named_output(&mut self, label: &str) -> &mut Self {
+        self.push_label(label);
+        if let Some(filter) = self.filters.last_mut() {
+            filter.pad_labels.push(label.to_string());
+        }
+        self
+    }
+
+    fn push_label(&mut self, label: &str) {
+        self.description.push('[');
+        self.description.push_str(label);
+        self.description.push(']');
+    }
+
+    pub fn filters(&self) -> &[FilterContext] {
+        &self.filters
+    }
+
+    pub fn build(&self) -> String {
+        self.description.clone()
+    }
+}
+
+// The filtergraph parser unescapes the filter arguments once, and the option
+// parser a second time, hence two rounds of escaping.
+fn escape_value(value: &str) -> String {
+    escape(&escape(value, "\\':="), "\\'[],;")
+}
+
+fn escape(value: &str, special: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if special.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_and_overlay() {
+        let mut builder = FilterGraphBuilder::new();
+        builder
+            .named_input("0:v")
+            .add_filter("scale", &[("", "1280"), ("", "720")])
+            .named_output("v")
+            .chain()
+            .named_input("v")
+            .named_input("1:v")
+            .add_filter("overlay", &[("x", "0"), ("y", "0")])
+            .named_output("out");
+
+        assert_eq!(
+            builder.build(),
+            "[0:v]scale=1280:720[v];[v][1:v]overlay=x=0:y=0[out]"
+        );
+        assert_eq!(
+            builder.filters(),
+            &[
+                FilterContext {
+                    name: "scale".to_string(),
+                    pad_labels: vec!["0:v".to_string(), "v".to_string()],
+                },
+                FilterContext {
+                    name: "overlay".to_string(),
+                    pad_labels: vec!["v".to_string(), "1:v".to_string(), "out".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn linked_filters_and_escaping() {
+        let mut builder = FilterGraphBuilder::new();
+        builder
+            .add_filter("null", &[])
+            .link()
+            .add_filter("drawtext", &[("text", "a:b,c")]);
+
+        assert_eq!(builder.build(), r"null,drawtext=text=a\\:b\,c");
+    }
+}
@@ -0,0 +1,11 @@
+mod buffersrc;
+pub use self::buffersrc::*;
+
+mod buffersink;
+pub use self::buffersink::*;
+
+mod graph_builder;
+pub use self::graph_builder::*;
+
+mod graph;
+pub use self::graph::*;
@@ -0,0 +1,64 @@
+use std::ptr;
+
+use libc::c_int;
+use {av_find_best_stream, AVCodec, AVFormatContext, AVMediaType};
+
+use avutil::check;
+use FFmpegError;
+
+/// Index of the best stream of the given type, along with a decoder for it.
+///
+/// Streams for which no decoder is available are skipped, in which case
+/// `AVERROR_DECODER_NOT_FOUND` is returned if nothing else matches.
+/// `wanted_stream_nb` and `related_stream` are -1 for automatic selection.
+pub unsafe fn find_best_stream(
+    fmtctx: *const AVFormatContext,
+    type_: AVMediaType,
+    wanted_stream_nb: c_int,
+    related_stream: c_int,
+    flags: c_int,
+) -> Result<(c_int, Option<*const AVCodec>), FFmpegError> {
+    let mut decoder = ptr::null_mut::<AVCodec>();
+    // decoder_ret is a pointer to const since FFmpeg 5.0
+    #[allow(clippy::unnecessary_cast)]
+    let decoder_ret = &mut decoder as *mut *mut AVCodec as *mut _;
+
+    let index = check(av_find_best_stream(
+        fmtctx as *mut _,
+        type_,
+        wanted_stream_nb,
+        related_stream,
+        decoder_ret,
+        flags,
+    ))?;
+
+    if decoder.is_null() {
+        Ok((index, None))
+    } else {
+        Ok((index, Some(decoder as *const AVCodec)))
+    }
+}
+
+unsafe fn best_stream(
+    fmtctx: *const AVFormatContext,
+    type_: AVMediaType,
+) -> Result<c_int, FFmpegError> {
+    check(av_find_best_stream(
+        fmtctx as *mut _,
+        type_,
+        -1,
+        -1,
+        ptr::null_mut(),
+        0,
+    ))
+}
+
+/// `AVERROR_STREAM_NOT_FOUND` if there is no video stream.
+pub unsafe fn best_video_stream(fmtctx: *const AVFormatContext) -> Result<c_int, FFmpegError> {
+    best_stream(fmtctx, AVMediaType::AVMEDIA_TYPE_VIDEO)
+}
+
+/// `AVERROR_STREAM_NOT_FOUND` if there is no audio stream.
+pub unsafe fn best_audio_stream(fmtctx: *const AVFormatContext) -> Result<c_int, FFmpegError> {
+    best_stream(fmtctx, AVMediaType::AVMEDIA_TYPE_AUDIO)
+}
@@ -0,0 +1,67 @@
+use std::ptr;
+
+use libc::c_uint;
+use {av_codec_get_id, av_codec_get_tag2, av_guess_format, AVCodecID, AVCodecTag};
+
+/// Codec of `tag` in a null-terminated list of codec tag tables, such as the
+/// `codec_tag` of a muxer or demuxer. `AV_CODEC_ID_NONE` if not found.
+pub unsafe fn codec_id_from_tag(table: *const *const AVCodecTag, tag: u32) -> AVCodecID {
+    av_codec_get_id(table, tag as c_uint)
+}
+
+/// Tag of `id` in a null-terminated list of codec tag tables, `None` if no
+/// table has one.
+pub unsafe fn tag_from_codec_id(table: *const *const AVCodecTag, id: AVCodecID) -> Option<u32> {
+    let mut tag: c_uint = 0;
+
+    if av_codec_get_tag2(table, id, &mut tag) != 0 {
+        Some(tag)
+    } else {
+        None
+    }
+}
+
+/// Tag of `codec_id` in a single `AV_CODEC_ID_NONE` terminated table such as
+/// the one returned by `avformat_get_riff_video_tags`.
+pub unsafe fn codec_tag_for_codec(codec_id: AVCodecID, table: *const AVCodecTag) -> Option<u32> {
+    let tables = [table, ptr::null()];
+    tag_from_codec_id(tables.as_ptr(), codec_id)
+}
+
+/// The RIFF (BITMAPINFOHEADER and WAVEFORMATEX) tag tables, taken from the
+/// `codec_tag` of the AVI muxer. `None` if libavformat was built without it.
+pub fn riff_codec_tag_lists() -> Option<*const *const AVCodecTag> {
+    unsafe {
+        let fmt = av_guess_format(b"avi\0".as_ptr() as *const _, ptr::null(), ptr::null());
+        if fmt.is_null() || (*fmt).codec_tag.is_null() {
+            return None;
+        }
+
+        Some((*fmt).codec_tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::register_all;
+
+    #[test]
+    fn riff_xvid() {
+        register_all();
+        let tables = riff_codec_tag_lists().expect("AVI muxer not available");
+
+        unsafe {
+            let xvid = u32::from_le_bytes(*b"XVID");
+            assert_eq!(
+                codec_id_from_tag(tables, xvid),
+                AVCodecID::AV_CODEC_ID_MPEG4
+            );
+
+            let tag = tag_from_codec_id(tables, AVCodecID::AV_CODEC_ID_MPEG4).unwrap();
+            assert_eq!(codec_id_from_tag(tables, tag), AVCodecID::AV_CODEC_ID_MPEG4);
+
+            assert_eq!(tag_from_codec_id(tables, AVCodecID::AV_CODEC_ID_NONE), None);
+        }
+    }
+}
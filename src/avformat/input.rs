@@ -0,0 +1,186 @@
+use std::ffi::CString;
+use std::ptr;
+
+use libc::c_int;
+use {
+    av_read_frame, avformat_close_input, avformat_find_stream_info, avformat_open_input,
+    avformat_seek_file, AVDictionary, AVFormatContext, AVInputFormat, AVPacket,
+};
+
+use super::{streams, Streams};
+use avutil::check;
+use FFmpegError;
+
+/// Demuxer context opened with `avformat_open_input`, closed with
+/// `avformat_close_input` on drop.
+pub struct InputContext(*mut AVFormatContext);
+
+// SAFETY: AVFormatContext has no thread affinity, so it can be used from
+// whichever thread owns the InputContext. The context does call back into
+// user code, through `interrupt_callback` and through the `opaque` pointer
+// handed to custom AVIOContexts; moving it to another thread relies on the
+// callbacks and opaque data set through `as_mut_ptr` being thread-safe.
+// See the safety_notes module.
+unsafe impl Send for InputContext {}
+
+impl InputContext {
+    /// `fmt` forces the input format instead of probing for it. Options
+    /// consumed by the demuxer are removed from `options`.
+    pub unsafe fn open(
+        url: &str,
+        fmt: Option<*const AVInputFormat>,
+        options: Option<*mut *mut AVDictionary>,
+    ) -> Result<Self, FFmpegError> {
+        let url = CString::new(url)?;
+        let mut ctx = ptr::null_mut();
+
+        // the context is freed by avformat_open_input on failure; fmt is
+        // only a pointer to const since FFmpeg 5.0
+        check(avformat_open_input(
+            &mut ctx,
+            url.as_ptr(),
+            fmt.unwrap_or(ptr::null()) as *mut _,
+            options.unwrap_or(ptr::null_mut()),
+        ))?;
+        Ok(InputContext(ctx))
+    }
+
+    pub fn as_ptr(&self) -> *const AVFormatContext {
+        self.0
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut AVFormatContext {
+        self.0
+    }
+
+    pub fn streams(&self) -> Streams<'_> {
+        unsafe { streams(self.0) }
+    }
+
+    /// Reads packets to fill in stream parameters missing from the headers.
+    ///
+    /// `options`, if given, is an array of `nb_streams` dictionaries, one per
+    /// stream, as for `avformat_find_stream_info`.
+    pub unsafe fn find_stream_info(
+        &mut self,
+        options: Option<*mut *mut AVDictionary>,
+    ) -> Result<(), FFmpegError> {
+        check(avformat_find_stream_info(
+            self.0,
+            options.unwrap_or(ptr::null_mut()),
+        ))?;
+        Ok(())
+    }
+
+    /// `FFmpegError::Eof` at the end of the input. The packet is blank on
+    /// error, and has to be unreferenced by the caller otherwise.
+    pub unsafe fn read_frame(&mut self, pkt: *mut AVPacket) -> Result<(), FFmpegError> {
+        check(av_read_frame(self.0, pkt))?;
+        Ok(())
+    }
+
+    /// Seeks to the keyframe closest to `ts` within `[min_ts, max_ts]`, in
+    /// the stream's time base, or `AV_TIME_BASE` units if `stream_index` is -1.
+    pub fn seek_file(
+        &mut self,
+        stream_index: c_int,
+        min_ts: i64,
+        ts: i64,
+        max_ts: i64,
+        flags: c_int,
+    ) -> Result<(), FFmpegError> {
+        unsafe {
+            check(avformat_seek_file(
+                self.0,
+                stream_index,
+                min_ts,
+                ts,
+                max_ts,
+                flags,
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for InputContext {
+    fn drop(&mut self) {
+        unsafe {
+            avformat_close_input(&mut self.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::register_all;
+    use std::env;
+    use std::fs;
+    use std::process;
+    use {av_packet_alloc, av_packet_free, av_packet_unref, AVCodecID, AVMediaType};
+
+    /// Mono 16-bit PCM WAV file holding `samples`.
+    fn wav(samples: &[i16]) -> Vec<u8> {
+        let data_size = samples.len() as u32 * 2;
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"RIFF");
+        buf.extend_from_slice(&(36 + data_size).to_le_bytes());
+        buf.extend_from_slice(b"WAVEfmt ");
+        buf.extend_from_slice(&16u32.to_le_bytes());
+        buf.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        buf.extend_from_slice(&1u16.to_le_bytes()); // channels
+        buf.extend_from_slice(&8000u32.to_le_bytes()); // sample rate
+        buf.extend_from_slice(&16000u32.to_le_bytes()); // byte rate
+        buf.extend_from_slice(&2u16.to_le_bytes()); // block align
+        buf.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+        buf.extend_from_slice(b"data");
+        buf.extend_from_slice(&data_size.to_le_bytes());
+        for sample in samples {
+            buf.extend_from_slice(&sample.to_le_bytes());
+        }
+        buf
+    }
+
+    #[test]
+    fn open_wav() {
+        register_all();
+        let path = env::temp_dir().join(format!("ffmpeg-sys-input-{}.wav", process::id()));
+        let samples: Vec<i16> = (1..801).map(|i| (i * 40) as i16).collect();
+        fs::write(&path, wav(&samples)).unwrap();
+
+        let mut input = unsafe { InputContext::open(path.to_str().unwrap(), None, None) }.unwrap();
+
+        unsafe {
+            input.find_stream_info(None).unwrap();
+            assert_eq!(input.streams().len(), 1);
+            let par = &*input.streams().next().unwrap().codec_parameters();
+            assert_eq!(par.codec_type, AVMediaType::AVMEDIA_TYPE_AUDIO);
+            assert_eq!(par.codec_id, AVCodecID::AV_CODEC_ID_PCM_S16LE);
+            assert_eq!(par.sample_rate, 8000);
+
+            let mut pkt = av_packet_alloc();
+            let mut size = 0;
+            loop {
+                match input.read_frame(pkt) {
+                    Ok(()) => {
+                        size += (*pkt).size;
+                        av_packet_unref(pkt);
+                    }
+                    Err(FFmpegError::Eof) => break,
+                    Err(err) => panic!("{}", err),
+                }
+            }
+            assert_eq!(size, 1600);
+
+            input.seek_file(-1, 0, 0, 0, 0).unwrap();
+            input.read_frame(pkt).unwrap();
+            assert_eq!(*(*pkt).data, 40);
+            av_packet_unref(pkt);
+            av_packet_free(&mut pkt);
+        }
+
+        drop(input);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+use std::marker::PhantomData;
+use std::slice;
+
+use libc::{c_int, c_uint};
+use {AVChapter, AVDictionary, AVFormatContext, AVProgram, AVRational};
+
+/// `fmtctx` must be valid for `'ctx`, and no chapters may be added to it
+/// while the slice is alive.
+pub unsafe fn format_chapters<'ctx>(fmtctx: *const AVFormatContext) -> &'ctx [*mut AVChapter] {
+    if (*fmtctx).chapters.is_null() {
+        return &[];
+    }
+
+    slice::from_raw_parts((*fmtctx).chapters, (*fmtctx).nb_chapters as usize)
+}
+
+/// `fmtctx` must be valid for `'ctx`, and no programs may be added to it
+/// while the slice is alive.
+pub unsafe fn format_programs<'ctx>(fmtctx: *const AVFormatContext) -> &'ctx [*mut AVProgram] {
+    if (*fmtctx).programs.is_null() {
+        return &[];
+    }
+
+    slice::from_raw_parts((*fmtctx).programs, (*fmtctx).nb_programs as usize)
+}
+
+/// Borrowed `AVChapter` of a format context that outlives `'ctx`.
+#[derive(Debug, Copy, Clone)]
+pub struct Chapter<'ctx>(*const AVChapter, PhantomData<&'ctx ()>);
+
+impl<'ctx> Chapter<'ctx> {
+    /// `ptr` must be a non-null chapter that stays valid for `'ctx`.
+    pub unsafe fn from_ptr(ptr: *const AVChapter) -> Self {
+        Chapter(ptr, PhantomData)
+    }
+
+    pub fn as_ptr(&self) -> *const AVChapter {
+        self.0
+    }
+
+    #[allow(clippy::unnecessary_cast)]
+    pub fn id(&self) -> i64 {
+        // id is an int before FFmpeg 5.0
+        unsafe { (*self.0).id as i64 }
+    }
+
+    pub fn time_base(&self) -> AVRational {
+        unsafe { (*self.0).time_base }
+    }
+
+    /// In `time_base` units.
+    pub fn start(&self) -> i64 {
+        unsafe { (*self.0).start }
+    }
+
+    /// In `time_base` units.
+    pub fn end(&self) -> i64 {
+        unsafe { (*self.0).end }
+    }
+
+    pub fn metadata(&self) -> *const AVDictionary {
+        unsafe { (*self.0).metadata }
+    }
+}
+
+/// Borrowed `AVProgram` of a format context that outlives `'ctx`.
+#[derive(Debug, Copy, Clone)]
+pub struct Program<'ctx>(*const AVProgram, PhantomData<&'ctx ()>);
+
+impl<'ctx> Program<'ctx> {
+    /// `ptr` must be a non-null program that stays valid for `'ctx`.
+    pub unsafe fn from_ptr(ptr: *const AVProgram) -> Self {
+        Program(ptr, PhantomData)
+    }
+
+    pub fn as_ptr(&self) -> *const AVProgram {
+        self.0
+    }
+
+    pub fn id(&self) -> c_int {
+        unsafe { (*self.0).id }
+    }
+
+    /// MPEG-TS program number, as found in the PAT.
+    pub fn program_num(&self) -> c_int {
+        unsafe { (*self.0).program_num }
+    }
+
+    pub fn pmt_pid(&self) -> c_int {
+        unsafe { (*self.0).pmt_pid }
+    }
+
+    pub fn pcr_pid(&self) -> c_int {
+        unsafe { (*self.0).pcr_pid }
+    }
+
+    /// Indices into the format context's streams.
+    pub fn stream_indices(&self) -> &'ctx [c_uint] {
+        unsafe {
+            if (*self.0).stream_index.is_null() {
+                return &[];
+            }
+
+            slice::from_raw_parts((*self.0).stream_index, (*self.0).nb_stream_indexes as usize)
+        }
+    }
+
+    pub fn metadata(&self) -> *const AVDictionary {
+        unsafe { (*self.0).metadata }
+    }
+}
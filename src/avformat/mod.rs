@@ -0,0 +1,26 @@
+mod stream;
+pub use self::stream::*;
+
+mod protocols;
+pub use self::protocols::*;
+
+mod metadata;
+pub use self::metadata::*;
+
+mod best_stream;
+pub use self::best_stream::*;
+
+mod input;
+pub use self::input::*;
+
+mod output;
+pub use self::output::*;
+
+mod specifier;
+pub use self::specifier::*;
+
+mod probe;
+pub use self::probe::*;
+
+mod codec_tag;
+pub use self::codec_tag::*;
@@ -0,0 +1,234 @@
+use std::ffi::CString;
+use std::ptr;
+
+use libc::{EINVAL, ENOMEM};
+use {
+    av_interleaved_write_frame, av_write_frame, av_write_trailer, avformat_alloc_output_context2,
+    avformat_free_context, avformat_new_stream, avformat_write_header, avio_closep, avio_open2,
+    AVDictionary, AVFormatContext, AVOutputFormat, AVPacket, AVFMT_NOFILE, AVIO_FLAG_WRITE,
+};
+
+use super::StreamRef;
+use avutil::check;
+use {FFmpegError, AVERROR};
+
+/// Muxer context allocated with `avformat_alloc_output_context2`.
+///
+/// On drop, the trailer is written if the header was but the trailer was
+/// not, the output file is closed and the context freed. Call
+/// `write_trailer` explicitly to see its errors.
+pub struct OutputContext {
+    ctx: *mut AVFormatContext,
+    header_written: bool,
+    trailer_written: bool,
+}
+
+// SAFETY: AVFormatContext has no thread affinity, so it can be used from
+// whichever thread owns the OutputContext. The context does call back into
+// user code, through `interrupt_callback` and through the `opaque` pointer
+// handed to custom AVIOContexts; moving it to another thread relies on the
+// callbacks and opaque data set through `as_mut_ptr` being thread-safe.
+// See the safety_notes module.
+unsafe impl Send for OutputContext {}
+
+impl OutputContext {
+    /// Creates a muxer for `filename`, guessing the format from its
+    /// extension unless `fmt` is given, and opens the file for writing if
+    /// the format needs one.
+    pub unsafe fn new_file(
+        filename: &str,
+        fmt: Option<*const AVOutputFormat>,
+    ) -> Result<Self, FFmpegError> {
+        let filename = CString::new(filename)?;
+        let mut ctx = ptr::null_mut();
+
+        // oformat is only a pointer to const since FFmpeg 5.0
+        check(avformat_alloc_output_context2(
+            &mut ctx,
+            fmt.unwrap_or(ptr::null()) as *mut _,
+            ptr::null(),
+            filename.as_ptr(),
+        ))?;
+        if ctx.is_null() {
+            return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+        }
+
+        let output = OutputContext {
+            ctx,
+            header_written: false,
+            trailer_written: false,
+        };
+        if output.needs_file() {
+            check(avio_open2(
+                &mut (*ctx).pb,
+                filename.as_ptr(),
+                AVIO_FLAG_WRITE,
+                ptr::null(),
+                ptr::null_mut(),
+            ))?;
+        }
+
+        Ok(output)
+    }
+
+    fn needs_file(&self) -> bool {
+        unsafe { (*(*self.ctx).oformat).flags & AVFMT_NOFILE == 0 }
+    }
+
+    pub fn as_ptr(&self) -> *const AVFormatContext {
+        self.ctx
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut AVFormatContext {
+        self.ctx
+    }
+
+    /// Adds a stream, whose codec parameters have to be filled in (e.g. with
+    /// `avcodec_parameters_from_context`) before writing the header.
+    pub fn new_stream(&mut self) -> Result<StreamRef<'_>, FFmpegError> {
+        unsafe {
+            let stream = avformat_new_stream(self.ctx, ptr::null());
+            if stream.is_null() {
+                return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+            }
+
+            Ok(StreamRef::from_ptr(stream))
+        }
+    }
+
+    /// Options consumed by the muxer are removed from `options`.
+    pub unsafe fn write_header(
+        &mut self,
+        options: Option<*mut *mut AVDictionary>,
+    ) -> Result<(), FFmpegError> {
+        check(avformat_write_header(
+            self.ctx,
+            options.unwrap_or(ptr::null_mut()),
+        ))?;
+        self.header_written = true;
+        Ok(())
+    }
+
+    /// Writes the packet as-is, leaving interleaving to the caller. A null
+    /// `pkt` flushes muxers that buffer data internally.
+    pub unsafe fn write_frame(&mut self, pkt: *mut AVPacket) -> Result<(), FFmpegError> {
+        check(av_write_frame(self.ctx, pkt))?;
+        Ok(())
+    }
+
+    /// Buffers the packet as needed to interleave the streams by dts. Takes
+    /// ownership of the packet's data, leaving it blank.
+    pub unsafe fn interleaved_write_frame(
+        &mut self,
+        pkt: *mut AVPacket,
+    ) -> Result<(), FFmpegError> {
+        check(av_interleaved_write_frame(self.ctx, pkt))?;
+        Ok(())
+    }
+
+    /// Fails with `EINVAL` if the header has not been written.
+    pub fn write_trailer(&mut self) -> Result<(), FFmpegError> {
+        if !self.header_written || self.trailer_written {
+            return Err(FFmpegError::Other(AVERROR(EINVAL)));
+        }
+
+        self.trailer_written = true;
+        unsafe {
+            check(av_write_trailer(self.ctx))?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for OutputContext {
+    fn drop(&mut self) {
+        unsafe {
+            if self.header_written && !self.trailer_written {
+                av_write_trailer(self.ctx);
+            }
+            if self.needs_file() {
+                avio_closep(&mut (*self.ctx).pb);
+            }
+            avformat_free_context(self.ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::register_all;
+    use std::env;
+    use std::fs;
+    use std::process;
+    use {
+        av_new_packet, av_packet_alloc, av_packet_free, av_packet_unref, AVCodecID, AVMediaType,
+        AVRational, AVSampleFormat,
+    };
+
+    #[cfg(feature = "ffmpeg_5_1")]
+    use av_channel_layout_default;
+
+    #[test]
+    fn mux_wav() {
+        register_all();
+        let path = env::temp_dir().join(format!("ffmpeg-sys-output-{}.wav", process::id()));
+        let samples: Vec<u8> = (0..1600).map(|i| i as u8).collect();
+
+        unsafe {
+            let mut output = OutputContext::new_file(path.to_str().unwrap(), None).unwrap();
+            {
+                let stream = &mut *output.new_stream().unwrap().as_ptr();
+                stream.time_base = AVRational { num: 1, den: 8000 };
+
+                let par = &mut *stream.codecpar;
+                par.codec_type = AVMediaType::AVMEDIA_TYPE_AUDIO;
+                par.codec_id = AVCodecID::AV_CODEC_ID_PCM_S16LE;
+                par.format = AVSampleFormat::AV_SAMPLE_FMT_S16 as _;
+                par.sample_rate = 8000;
+                #[cfg(feature = "ffmpeg_5_1")]
+                av_channel_layout_default(&mut par.ch_layout, 1);
+                #[cfg(not(feature = "ffmpeg_5_1"))]
+                {
+                    par.channels = 1;
+                }
+            }
+            output.write_header(None).unwrap();
+
+            let mut pkt = av_packet_alloc();
+            for (i, chunk) in samples.chunks(400).enumerate() {
+                check(av_new_packet(pkt, chunk.len() as _)).unwrap();
+                ptr::copy_nonoverlapping(chunk.as_ptr(), (*pkt).data, chunk.len());
+                (*pkt).pts = i as i64 * 200;
+                (*pkt).dts = (*pkt).pts;
+                (*pkt).duration = 200;
+                output.write_frame(pkt).unwrap();
+                av_packet_unref(pkt);
+            }
+            av_packet_free(&mut pkt);
+
+            output.write_trailer().unwrap();
+            assert!(output.write_trailer().is_err());
+        }
+
+        let data = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(&data[..4], b"RIFF");
+        assert_eq!(&data[8..12], b"WAVE");
+        assert!(data.ends_with(&samples));
+    }
+
+    #[test]
+    fn trailer_without_header() {
+        register_all();
+        let path = env::temp_dir().join(format!("ffmpeg-sys-output-{}.nut", process::id()));
+        unsafe {
+            let mut output = OutputContext::new_file(path.to_str().unwrap(), None).unwrap();
+            assert_eq!(
+                output.write_trailer(),
+                Err(FFmpegError::Other(AVERROR(EINVAL)))
+            );
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,105 @@
+use std::ffi::CString;
+use std::mem;
+use std::ptr;
+
+use libc::c_int;
+use {av_probe_input_format3, AVInputFormat, AVProbeData, AVPROBE_PADDING_SIZE};
+
+/// Outcome of probing a buffer, `format` is null if nothing matched.
+#[derive(Debug, Copy, Clone)]
+pub struct ProbeResult {
+    pub format: *const AVInputFormat,
+    /// Between 0 and `AVPROBE_SCORE_MAX`.
+    pub score: c_int,
+}
+
+/// Guesses the input format of `data`, optionally helped by a file name
+/// whose extension is taken into account, as `av_probe_input_format3` does.
+///
+/// `data` is copied since FFmpeg requires `AVPROBE_PADDING_SIZE` zeroed
+/// bytes after the probed buffer.
+pub fn probe_input_format(data: &[u8], filename_hint: Option<&str>) -> ProbeResult {
+    let mut buf = Vec::with_capacity(data.len() + AVPROBE_PADDING_SIZE as usize);
+    buf.extend_from_slice(data);
+    buf.resize(data.len() + AVPROBE_PADDING_SIZE as usize, 0);
+
+    // a hint with an interior nul can't match any extension anyway
+    let filename = filename_hint.and_then(|name| CString::new(name).ok());
+
+    unsafe {
+        let mut pd: AVProbeData = mem::zeroed();
+        pd.filename = filename.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+        pd.buf = buf.as_mut_ptr();
+        pd.buf_size = data.len() as c_int;
+
+        let mut score = 0;
+        // the return value is only a pointer to const since FFmpeg 5.0
+        #[allow(clippy::unnecessary_cast)]
+        let format = av_probe_input_format3(&pd, 1, &mut score) as *const AVInputFormat;
+
+        ProbeResult { format, score }
+    }
+}
+
+/// Like `probe_input_format`, but `None` unless a format was found with a
+/// score above `min_score`.
+pub fn probe_input_format_score_threshold(
+    data: &[u8],
+    filename_hint: Option<&str>,
+    min_score: c_int,
+) -> Option<*const AVInputFormat> {
+    let result = probe_input_format(data, filename_hint);
+
+    if !result.format.is_null() && result.score > min_score {
+        Some(result.format)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::register_all;
+    use std::ffi::CStr;
+    use AVPROBE_SCORE_MAX;
+
+    // smallest well-formed baseline JPEG: one 8-bit gray pixel
+    const JPEG: &[u8] = &[
+        0xff, 0xd8, // SOI
+        0xff, 0xe0, 0x00, 0x10, b'J', b'F', b'I', b'F', 0x00, 0x01, 0x01, 0x00, 0x00, 0x01, 0x00,
+        0x01, 0x00, 0x00, // APP0
+        0xff, 0xc0, 0x00, 0x0b, 0x08, 0x00, 0x01, 0x00, 0x01, 0x01, 0x01, 0x11, 0x00, // SOF0
+        0xff, 0xda, 0x00, 0x08, 0x01, 0x01, 0x00, 0x00, 0x3f, 0x00, // SOS
+        0x00, // scan data
+        0xff, 0xd9, // EOI
+    ];
+
+    fn format_name(format: *const AVInputFormat) -> &'static str {
+        unsafe { CStr::from_ptr((*format).name).to_str().unwrap() }
+    }
+
+    #[test]
+    fn jpeg() {
+        register_all();
+
+        for &hint in &[None, Some("test.jpg")] {
+            let result = probe_input_format(JPEG, hint);
+            assert!(!result.format.is_null());
+            let name = format_name(result.format);
+            assert!(name.contains("image2") || name.contains("jpeg"), "{}", name);
+            assert!(result.score > 0 && result.score <= AVPROBE_SCORE_MAX as c_int);
+        }
+    }
+
+    #[test]
+    fn score_threshold() {
+        register_all();
+
+        let format = probe_input_format_score_threshold(JPEG, None, 25).unwrap();
+        assert!(format_name(format).contains("jpeg"));
+        assert!(
+            probe_input_format_score_threshold(JPEG, None, AVPROBE_SCORE_MAX as c_int).is_none()
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use libc::{c_int, c_void};
+use {avio_check, avio_enum_protocols, avio_find_protocol_name};
+
+use avutil::check;
+use FFmpegError;
+
+/// Iterator over the names of the protocols registered in libavformat.
+pub struct Protocols {
+    opaque: *mut c_void,
+    output: c_int,
+}
+
+impl Iterator for Protocols {
+    type Item = &'static str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            let name = avio_enum_protocols(&mut self.opaque, self.output);
+            if name.is_null() {
+                return None;
+            }
+
+            // protocol names are static ASCII strings
+            Some(CStr::from_ptr(name).to_str().unwrap())
+        }
+    }
+}
+
+pub fn input_protocols() -> Protocols {
+    Protocols {
+        opaque: ptr::null_mut(),
+        output: 0,
+    }
+}
+
+pub fn output_protocols() -> Protocols {
+    Protocols {
+        opaque: ptr::null_mut(),
+        output: 1,
+    }
+}
+
+/// Name of the protocol that would handle `url`.
+pub fn protocol_from_url(url: &str) -> Option<&'static str> {
+    let url = CString::new(url).ok()?;
+
+    unsafe {
+        let name = avio_find_protocol_name(url.as_ptr());
+        if name.is_null() {
+            return None;
+        }
+
+        Some(CStr::from_ptr(name).to_str().unwrap())
+    }
+}
+
+/// `avio_check`: which of the `AVIO_FLAG_READ`/`AVIO_FLAG_WRITE` access
+/// `flags` `url` allows; with 0 it only checks that the resource exists.
+pub fn check_url(url: &str, flags: c_int) -> Result<c_int, FFmpegError> {
+    let url = CString::new(url)?;
+
+    unsafe { check(avio_check(url.as_ptr(), flags)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_protocol() {
+        assert!(input_protocols().any(|name| name == "file"));
+        assert!(output_protocols().any(|name| name == "file"));
+        assert_eq!(protocol_from_url("/tmp/test.mp4"), Some("file"));
+        assert_eq!(protocol_from_url("file:test.mp4"), Some("file"));
+    }
+}
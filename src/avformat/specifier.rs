@@ -0,0 +1,118 @@
+use std::ffi::CString;
+use std::iter::Enumerate;
+use std::slice;
+
+use libc::c_int;
+use {avformat_match_stream_specifier, AVFormatContext, AVStream};
+
+use avutil::check;
+use FFmpegError;
+
+/// Whether `st` matches a stream specifier such as `"v:0"`, `"a"` or
+/// `"i:1:a"`, as described in the "Stream specifiers" section of the
+/// ffmpeg documentation.
+///
+/// `st` must be a stream of `fmtctx`. Invalid specifiers are an error.
+pub unsafe fn match_stream_specifier(
+    fmtctx: *mut AVFormatContext,
+    st: *mut AVStream,
+    spec: &str,
+) -> Result<bool, FFmpegError> {
+    let spec = CString::new(spec)?;
+
+    Ok(check(avformat_match_stream_specifier(fmtctx, st, spec.as_ptr()))? > 0)
+}
+
+/// Iterator over the streams of a format context matching a specifier,
+/// along with their indices.
+///
+/// Yields a single error and stops if the specifier is invalid.
+pub struct MatchingStreams<'ctx> {
+    fmtctx: *mut AVFormatContext,
+    spec: CString,
+    streams: Enumerate<slice::Iter<'ctx, *mut AVStream>>,
+}
+
+impl<'ctx> Iterator for MatchingStreams<'ctx> {
+    type Item = Result<(*mut AVStream, c_int), FFmpegError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, &stream) in &mut self.streams {
+            let ret =
+                unsafe { avformat_match_stream_specifier(self.fmtctx, stream, self.spec.as_ptr()) };
+
+            match check(ret) {
+                Ok(0) => {}
+                Ok(_) => return Some(Ok((stream, index as c_int))),
+                Err(e) => {
+                    self.streams = [].iter().enumerate();
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// `fmtctx` must be valid for `'ctx`, and no streams may be added to it
+/// while the iterator is alive.
+pub unsafe fn streams_matching_specifier<'ctx>(
+    fmtctx: *mut AVFormatContext,
+    spec: &str,
+) -> Result<MatchingStreams<'ctx>, FFmpegError> {
+    let spec = CString::new(spec)?;
+    let streams = if (*fmtctx).streams.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts((*fmtctx).streams, (*fmtctx).nb_streams as usize)
+    };
+
+    Ok(MatchingStreams {
+        fmtctx,
+        spec,
+        streams: streams.iter().enumerate(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+    use {avformat_alloc_context, avformat_free_context, avformat_new_stream, AVMediaType};
+
+    #[test]
+    fn video_and_audio() {
+        unsafe {
+            let ctx = avformat_alloc_context();
+            for &codec_type in &[
+                AVMediaType::AVMEDIA_TYPE_VIDEO,
+                AVMediaType::AVMEDIA_TYPE_AUDIO,
+            ] {
+                let stream = avformat_new_stream(ctx, ptr::null());
+                (*(*stream).codecpar).codec_type = codec_type;
+            }
+            let video = *(*ctx).streams;
+            let audio = *(*ctx).streams.offset(1);
+
+            assert!(match_stream_specifier(ctx, video, "v").unwrap());
+            assert!(!match_stream_specifier(ctx, audio, "v").unwrap());
+            assert!(match_stream_specifier(ctx, audio, "a:0").unwrap());
+            assert!(match_stream_specifier(ctx, audio, "1").unwrap());
+            assert!(match_stream_specifier(ctx, video, "x").is_err());
+
+            let indices = |spec| {
+                streams_matching_specifier(ctx, spec)
+                    .unwrap()
+                    .map(|m| m.map(|(_, index)| index))
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            assert_eq!(indices("v"), Ok(vec![0]));
+            assert_eq!(indices("a"), Ok(vec![1]));
+            assert_eq!(indices("s"), Ok(vec![]));
+            assert!(indices("x").is_err());
+
+            avformat_free_context(ctx);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::marker::PhantomData;
+use std::slice;
+
+use libc::c_int;
+use {AVCodecParameters, AVDictionary, AVFormatContext, AVRational, AVStream};
+
+/// Borrowed `AVStream` of a format context that outlives `'ctx`.
+#[derive(Debug, Copy, Clone)]
+pub struct StreamRef<'ctx>(*mut AVStream, PhantomData<&'ctx ()>);
+
+impl<'ctx> StreamRef<'ctx> {
+    /// `ptr` must be a non-null stream that stays valid for `'ctx`.
+    pub unsafe fn from_ptr(ptr: *mut AVStream) -> Self {
+        StreamRef(ptr, PhantomData)
+    }
+
+    pub fn as_ptr(&self) -> *mut AVStream {
+        self.0
+    }
+
+    pub fn codec_parameters(&self) -> *const AVCodecParameters {
+        unsafe { (*self.0).codecpar }
+    }
+
+    pub fn time_base(&self) -> AVRational {
+        unsafe { (*self.0).time_base }
+    }
+
+    pub fn avg_frame_rate(&self) -> AVRational {
+        unsafe { (*self.0).avg_frame_rate }
+    }
+
+    pub fn r_frame_rate(&self) -> AVRational {
+        unsafe { (*self.0).r_frame_rate }
+    }
+
+    /// In `time_base` units, `AV_NOPTS_VALUE` if unknown.
+    pub fn duration(&self) -> i64 {
+        unsafe { (*self.0).duration }
+    }
+
+    /// In `time_base` units, `AV_NOPTS_VALUE` if unknown.
+    pub fn start_time(&self) -> i64 {
+        unsafe { (*self.0).start_time }
+    }
+
+    /// 0 if unknown.
+    pub fn nb_frames(&self) -> i64 {
+        unsafe { (*self.0).nb_frames }
+    }
+
+    pub fn index(&self) -> c_int {
+        unsafe { (*self.0).index }
+    }
+
+    /// Format-specific stream id.
+    pub fn id(&self) -> c_int {
+        unsafe { (*self.0).id }
+    }
+
+    pub fn metadata(&self) -> *mut AVDictionary {
+        unsafe { (*self.0).metadata }
+    }
+
+    /// Combination of `AV_DISPOSITION_*` flags.
+    pub fn disposition(&self) -> c_int {
+        unsafe { (*self.0).disposition }
+    }
+}
+
+/// Iterator over the streams of a format context.
+pub struct Streams<'ctx>(slice::Iter<'ctx, *mut AVStream>);
+
+impl<'ctx> Iterator for Streams<'ctx> {
+    type Item = StreamRef<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|&stream| unsafe { StreamRef::from_ptr(stream) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'ctx> ExactSizeIterator for Streams<'ctx> {}
+
+/// `fmtctx` must be valid for `'ctx`, and no streams may be added to it
+/// while the iterator is alive.
+pub unsafe fn streams<'ctx>(fmtctx: *const AVFormatContext) -> Streams<'ctx> {
+    if (*fmtctx).streams.is_null() {
+        return Streams([].iter());
+    }
+
+    Streams(slice::from_raw_parts((*fmtctx).streams, (*fmtctx).nb_streams as usize).iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+    use {avformat_alloc_context, avformat_free_context, avformat_new_stream};
+
+    #[test]
+    fn accessors_match_fields() {
+        unsafe {
+            let ctx = avformat_alloc_context();
+            assert!(!ctx.is_null());
+            assert_eq!(streams(ctx).len(), 0);
+
+            for i in 0..2 {
+                let st = avformat_new_stream(ctx, ptr::null());
+                assert!(!st.is_null());
+                (*st).id = 0x100 + i;
+                (*st).time_base = AVRational { num: 1, den: 90000 };
+                (*st).avg_frame_rate = AVRational { num: 25, den: 1 };
+                (*st).nb_frames = 42;
+                (*st).disposition = 1;
+            }
+
+            assert_eq!(streams(ctx).len(), 2);
+            for (i, stream) in streams(ctx).enumerate() {
+                let st = *(*ctx).streams.add(i);
+                assert_eq!(stream.as_ptr(), st);
+                assert_eq!(stream.index(), (*st).index);
+                assert_eq!(stream.index(), i as c_int);
+                assert_eq!(stream.id(), (*st).id);
+                assert_eq!(stream.codec_parameters(), (*st).codecpar as *const _);
+                assert_eq!(stream.time_base(), (*st).time_base);
+                assert_eq!(stream.avg_frame_rate(), (*st).avg_frame_rate);
+                assert_eq!(stream.r_frame_rate(), (*st).r_frame_rate);
+                assert_eq!(stream.duration(), (*st).duration);
+                assert_eq!(stream.start_time(), (*st).start_time);
+                assert_eq!(stream.nb_frames(), 42);
+                assert_eq!(stream.metadata(), (*st).metadata);
+                assert_eq!(stream.disposition(), (*st).disposition);
+            }
+
+            avformat_free_context(ctx);
+        }
+    }
+}
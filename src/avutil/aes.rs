@@ -0,0 +1,244 @@
+use std::ptr;
+
+use libc::{c_int, c_void, EINVAL, ENOMEM};
+use {av_aes_alloc, av_aes_crypt, av_aes_init, av_aes_size, av_free, AVAES};
+
+use super::error::check;
+use super::{FFmpegError, AVERROR};
+
+unsafe fn aes_crypt(
+    ctx: *mut AVAES,
+    decrypt: bool,
+    dst: &mut [[u8; 16]],
+    src: &[[u8; 16]],
+    iv: Option<&mut [u8; 16]>,
+) -> Result<(), FFmpegError> {
+    if dst.len() < src.len() {
+        return Err(FFmpegError::Other(AVERROR(EINVAL)));
+    }
+
+    av_aes_crypt(
+        ctx,
+        dst.as_mut_ptr() as *mut u8,
+        src.as_ptr() as *const u8,
+        src.len() as c_int,
+        iv.map_or(ptr::null_mut(), |iv| iv.as_mut_ptr()),
+        decrypt as c_int,
+    );
+
+    Ok(())
+}
+
+/// Heap allocated AES context, with a 128, 192 or 256 bit key. Passing an IV
+/// to `crypt` selects CBC mode, otherwise ECB is used.
+pub struct AesContext(*mut AVAES, bool);
+
+// SAFETY: AVAES has no thread affinity and is only mutated through &mut self;
+// see the safety_notes module.
+unsafe impl Send for AesContext {}
+
+impl AesContext {
+    pub fn new(key: &[u8], decrypt: bool) -> Result<Self, FFmpegError> {
+        unsafe {
+            let ctx = av_aes_alloc();
+            if ctx.is_null() {
+                return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+            }
+            let aes = AesContext(ctx, decrypt);
+            check(av_aes_init(
+                aes.0,
+                key.as_ptr(),
+                (key.len() * 8) as c_int,
+                decrypt as c_int,
+            ))?;
+            Ok(aes)
+        }
+    }
+
+    /// Fails with `AVERROR(EINVAL)` if `dst` is shorter than `src`.
+    pub fn crypt(
+        &mut self,
+        dst: &mut [[u8; 16]],
+        src: &[[u8; 16]],
+        iv: Option<&mut [u8; 16]>,
+    ) -> Result<(), FFmpegError> {
+        unsafe { aes_crypt(self.0, self.1, dst, src, iv) }
+    }
+}
+
+impl Drop for AesContext {
+    fn drop(&mut self) {
+        unsafe {
+            av_free(self.0 as *mut c_void);
+        }
+    }
+}
+
+// AVAES is opaque in the public headers, its size is only known at runtime
+// through av_aes_size (288 bytes on 64-bit targets as of FFmpeg 7).
+#[repr(C, align(16))]
+struct AesStorage([u8; 512]);
+
+/// `AesContext` without the heap allocation.
+pub struct AesContextStack(AesStorage, bool);
+
+impl AesContextStack {
+    pub fn new(key: &[u8], decrypt: bool) -> Result<Self, FFmpegError> {
+        unsafe {
+            assert!(av_aes_size as usize <= std::mem::size_of::<AesStorage>());
+
+            let mut aes = AesContextStack(AesStorage([0; 512]), decrypt);
+            check(av_aes_init(
+                aes.as_mut_ptr(),
+                key.as_ptr(),
+                (key.len() * 8) as c_int,
+                decrypt as c_int,
+            ))?;
+            Ok(aes)
+        }
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut AVAES {
+        self.0 .0.as_mut_ptr() as *mut AVAES
+    }
+
+    pub fn crypt(
+        &mut self,
+        dst: &mut [[u8; 16]],
+        src: &[[u8; 16]],
+        iv: Option<&mut [u8; 16]>,
+    ) -> Result<(), FFmpegError> {
+        unsafe { aes_crypt(self.as_mut_ptr(), self.1, dst, src, iv) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::unhex;
+
+    // FIPS-197 appendix C.1
+    const KEY: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+    const PLAINTEXT: [u8; 16] = [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff,
+    ];
+    const CIPHERTEXT: [u8; 16] = [
+        0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5,
+        0x5a,
+    ];
+
+    #[test]
+    fn aes128_heap() {
+        let mut out = [[0u8; 16]];
+
+        AesContext::new(&KEY, false)
+            .unwrap()
+            .crypt(&mut out, &[PLAINTEXT], None)
+            .unwrap();
+        assert_eq!(out, [CIPHERTEXT]);
+
+        AesContext::new(&KEY, true)
+            .unwrap()
+            .crypt(&mut out, &[CIPHERTEXT], None)
+            .unwrap();
+        assert_eq!(out, [PLAINTEXT]);
+    }
+
+    #[test]
+    fn aes128_stack() {
+        let mut out = [[0u8; 16]];
+
+        AesContextStack::new(&KEY, false)
+            .unwrap()
+            .crypt(&mut out, &[PLAINTEXT], None)
+            .unwrap();
+        assert_eq!(out, [CIPHERTEXT]);
+
+        AesContextStack::new(&KEY, true)
+            .unwrap()
+            .crypt(&mut out, &[CIPHERTEXT], None)
+            .unwrap();
+        assert_eq!(out, [PLAINTEXT]);
+    }
+
+    fn blocks(hex: &str) -> Vec<[u8; 16]> {
+        unhex(hex)
+            .chunks(16)
+            .map(|chunk| {
+                let mut block = [0u8; 16];
+                block.copy_from_slice(chunk);
+                block
+            })
+            .collect()
+    }
+
+    // NIST SP 800-38A, F.2.1 and F.2.2 (CBC-AES128)
+    const CBC_KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+    const CBC_IV: &str = "000102030405060708090a0b0c0d0e0f";
+    const CBC_PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172a\
+                                 ae2d8a571e03ac9c9eb76fac45af8e51\
+                                 30c81c46a35ce411e5fbc1191a0a52ef\
+                                 f69f2445df4f9b17ad2b417be66c3710";
+    const CBC_CIPHERTEXT: &str = "7649abac8119b246cee98e9b12e9197d\
+                                  5086cb9b507219ee95db113a917678b2\
+                                  73bed6b8e3c1743b7116e69e22229516\
+                                  3ff1caa1681fac09120eca307586e1a7";
+
+    fn cbc_iv() -> [u8; 16] {
+        blocks(CBC_IV)[0]
+    }
+
+    #[test]
+    fn aes128_cbc() {
+        let key = unhex(CBC_KEY);
+        let plaintext = blocks(CBC_PLAINTEXT);
+        let ciphertext = blocks(CBC_CIPHERTEXT);
+        let mut out = vec![[0u8; 16]; 4];
+
+        AesContext::new(&key, false)
+            .unwrap()
+            .crypt(&mut out, &plaintext, Some(&mut cbc_iv()))
+            .unwrap();
+        assert_eq!(out, ciphertext);
+
+        AesContext::new(&key, true)
+            .unwrap()
+            .crypt(&mut out, &ciphertext, Some(&mut cbc_iv()))
+            .unwrap();
+        assert_eq!(out, plaintext);
+    }
+
+    #[test]
+    fn aes128_cbc_chained() {
+        // the IV is updated in place, so a message can be fed in pieces
+        let mut aes = AesContextStack::new(&unhex(CBC_KEY), false).unwrap();
+        let plaintext = blocks(CBC_PLAINTEXT);
+        let mut iv = cbc_iv();
+        let mut out = vec![[0u8; 16]; 4];
+
+        aes.crypt(&mut out[..1], &plaintext[..1], Some(&mut iv))
+            .unwrap();
+        aes.crypt(&mut out[1..], &plaintext[1..], Some(&mut iv))
+            .unwrap();
+        assert_eq!(out, blocks(CBC_CIPHERTEXT));
+    }
+
+    #[test]
+    fn short_destination() {
+        let mut aes = AesContext::new(&KEY, false).unwrap();
+        let mut out = [[0u8; 16]];
+        assert_eq!(
+            aes.crypt(&mut out, &[PLAINTEXT, PLAINTEXT], None),
+            Err(FFmpegError::Other(AVERROR(EINVAL)))
+        );
+    }
+
+    #[test]
+    fn invalid_key_size() {
+        assert!(AesContext::new(&KEY[..15], false).is_err());
+    }
+}
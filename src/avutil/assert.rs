@@ -0,0 +1,60 @@
+use std::process;
+
+use libc::c_int;
+
+/// Rust counterpart of the C `av_assert0` macro, aborting if `cond` is 0.
+///
+/// Meant for callbacks invoked by FFmpeg, where unwinding out of a panic
+/// into C code is not an option; elsewhere use the `av_assert0!` macro.
+pub fn av_assert0_ffi(cond: c_int) {
+    if cond == 0 {
+        eprintln!("Assertion failed in an FFmpeg callback");
+        process::abort();
+    }
+}
+
+/// Like the C `av_assert0`, checked in all builds, but panics instead of
+/// aborting.
+#[macro_export]
+macro_rules! av_assert0 {
+    ($cond:expr) => {
+        if !$cond {
+            panic!("av_assert0 failed: {}", stringify!($cond));
+        }
+    };
+}
+
+/// Like the C `av_assert1`, only checked with `debug_assertions`.
+#[macro_export]
+macro_rules! av_assert1 {
+    ($cond:expr) => {
+        if cfg!(debug_assertions) && !$cond {
+            panic!("av_assert1 failed: {}", stringify!($cond));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passing_assertions() {
+        av_assert0!(true);
+        av_assert1!(1 + 1 == 2);
+        av_assert0_ffi(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "av_assert0 failed: 1 + 1 == 3")]
+    fn failing_assert0() {
+        av_assert0!(1 + 1 == 3);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "av_assert1 failed: false")]
+    fn failing_assert1() {
+        av_assert1!(false);
+    }
+}
@@ -0,0 +1,130 @@
+use std::convert::TryFrom;
+use std::f64;
+
+use libc::EINVAL;
+use {AVDownmixInfo, AVFrame, AVFrameSideDataType, AVReplayGain};
+
+use super::display::frame_side_data;
+use super::error::{FFmpegError, AVERROR};
+
+/// The `AV_FRAME_DATA_REPLAYGAIN` side data of `frame`, if present; it is
+/// only valid for as long as the side data is attached to the frame.
+pub unsafe fn replaygain_from_frame<'a>(frame: *const AVFrame) -> Option<&'a AVReplayGain> {
+    frame_side_data(frame, AVFrameSideDataType::AV_FRAME_DATA_REPLAYGAIN)
+}
+
+/// The `AV_FRAME_DATA_DOWNMIX_INFO` side data of `frame`, if present; it is
+/// only valid for as long as the side data is attached to the frame.
+pub unsafe fn downmix_info_from_frame<'a>(frame: *const AVFrame) -> Option<&'a AVDownmixInfo> {
+    frame_side_data(frame, AVFrameSideDataType::AV_FRAME_DATA_DOWNMIX_INFO)
+}
+
+/// `AVReplayGain` converted to decibels and linear peak amplitudes, where
+/// 1.0 is full scale.
+///
+/// An unknown album gain is NaN and unknown peaks are 0.0.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ReplayGainDisplay {
+    pub track_gain_db: f64,
+    pub track_peak: f64,
+    pub album_gain_db: f64,
+    pub album_peak: f64,
+}
+
+// gains are stored in microbels, peaks with 100000 representing full scale
+const REPLAYGAIN_SCALE: f64 = 100000.0;
+
+impl<'a> TryFrom<&'a AVReplayGain> for ReplayGainDisplay {
+    type Error = FFmpegError;
+
+    /// Fails with `AVERROR(EINVAL)` if the track gain is unknown.
+    fn try_from(rg: &'a AVReplayGain) -> Result<Self, Self::Error> {
+        if rg.track_gain == i32::MIN {
+            return Err(FFmpegError::Other(AVERROR(EINVAL)));
+        }
+
+        let album_gain_db = if rg.album_gain == i32::MIN {
+            f64::NAN
+        } else {
+            f64::from(rg.album_gain) / REPLAYGAIN_SCALE
+        };
+
+        Ok(ReplayGainDisplay {
+            track_gain_db: f64::from(rg.track_gain) / REPLAYGAIN_SCALE,
+            track_peak: f64::from(rg.track_peak) / REPLAYGAIN_SCALE,
+            album_gain_db,
+            album_peak: f64::from(rg.album_peak) / REPLAYGAIN_SCALE,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+    use std::ptr;
+    use {av_frame_alloc, av_frame_free, av_frame_new_side_data};
+
+    #[test]
+    fn replaygain_to_decibels() {
+        let rg = AVReplayGain {
+            track_gain: -650_000,
+            track_peak: 98_765,
+            album_gain: 120_000,
+            album_peak: 100_000,
+        };
+
+        let display = ReplayGainDisplay::try_from(&rg).unwrap();
+        assert_eq!(display.track_gain_db, -6.5);
+        assert_eq!(display.track_peak, 0.98765);
+        assert_eq!(display.album_gain_db, 1.2);
+        assert_eq!(display.album_peak, 1.0);
+    }
+
+    #[test]
+    fn replaygain_unknown_values() {
+        let mut rg = AVReplayGain {
+            track_gain: 10_000,
+            track_peak: 0,
+            album_gain: i32::MIN,
+            album_peak: 0,
+        };
+
+        let display = ReplayGainDisplay::try_from(&rg).unwrap();
+        assert_eq!(display.track_gain_db, 0.1);
+        assert!(display.album_gain_db.is_nan());
+
+        rg.track_gain = i32::MIN;
+        assert!(ReplayGainDisplay::try_from(&rg).is_err());
+    }
+
+    #[test]
+    fn replaygain_side_data() {
+        unsafe {
+            let mut frame = av_frame_alloc();
+            assert!(replaygain_from_frame(frame).is_none());
+
+            let side_data = av_frame_new_side_data(
+                frame,
+                AVFrameSideDataType::AV_FRAME_DATA_REPLAYGAIN,
+                mem::size_of::<AVReplayGain>() as _,
+            );
+            assert!(!side_data.is_null());
+            ptr::write(
+                (*side_data).data as *mut AVReplayGain,
+                AVReplayGain {
+                    track_gain: -100_000,
+                    track_peak: 50_000,
+                    album_gain: i32::MIN,
+                    album_peak: 0,
+                },
+            );
+
+            let rg = replaygain_from_frame(frame).unwrap();
+            assert_eq!(rg.track_gain, -100_000);
+            assert!(downmix_info_from_frame(frame).is_none());
+
+            av_frame_free(&mut frame);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::mem;
+
+use libc::{c_int, ENOMEM};
+use {
+    av_frame_alloc, av_frame_clone, av_frame_copy_props, av_frame_free, av_frame_make_writable,
+    av_frame_unref, AVFrame,
+};
+#[cfg(test)]
+use {av_frame_get_buffer, AVPixelFormat};
+
+use super::error::check;
+use super::{FFmpegError, AVERROR};
+
+/// Owned `AVFrame`, freed with `av_frame_free` on drop.
+pub struct Frame(*mut AVFrame);
+
+// SAFETY: AVFrame is movable between threads, and its buffers are reference
+// counted atomically; see the safety_notes module.
+unsafe impl Send for Frame {}
+
+impl Frame {
+    pub fn new() -> Result<Self, FFmpegError> {
+        let frame = unsafe { av_frame_alloc() };
+        if frame.is_null() {
+            return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+        }
+
+        Ok(Frame(frame))
+    }
+
+    /// Takes ownership of a frame allocated with `av_frame_alloc`.
+    pub unsafe fn from_raw(frame: *mut AVFrame) -> Self {
+        Frame(frame)
+    }
+
+    pub fn into_raw(self) -> *mut AVFrame {
+        let frame = self.0;
+        mem::forget(self);
+        frame
+    }
+
+    pub fn as_ptr(&self) -> *const AVFrame {
+        self.0
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut AVFrame {
+        self.0
+    }
+
+    /// New reference to the same buffers, see `av_frame_clone`.
+    ///
+    /// Frames that are not reference counted get their data copied instead,
+    /// which fails if the format and dimensions do not describe a valid
+    /// buffer, e.g. for an empty frame on older FFmpeg versions.
+    /// `av_frame_clone` does not report the cause, so every failure is
+    /// returned as `AVERROR(ENOMEM)`.
+    pub fn try_clone(&self) -> Result<Frame, FFmpegError> {
+        let frame = unsafe { av_frame_clone(self.0) };
+        if frame.is_null() {
+            return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+        }
+
+        Ok(Frame(frame))
+    }
+
+    /// Drops all buffer references and resets the fields to their defaults.
+    pub fn unref(&mut self) {
+        unsafe {
+            av_frame_unref(self.0);
+        }
+    }
+
+    /// Copies the data if it is shared with another reference, so that it can
+    /// be written to.
+    pub fn make_writable(&mut self) -> Result<(), FFmpegError> {
+        unsafe {
+            check(av_frame_make_writable(self.0))?;
+        }
+        Ok(())
+    }
+
+    /// Copies the metadata (timestamps, side data, ...) but not the data.
+    pub fn copy_props(&mut self, src: &Frame) -> Result<(), FFmpegError> {
+        unsafe {
+            check(av_frame_copy_props(self.0, src.0))?;
+        }
+        Ok(())
+    }
+
+    pub fn pts(&self) -> i64 {
+        unsafe { (*self.0).pts }
+    }
+
+    pub fn pkt_dts(&self) -> i64 {
+        unsafe { (*self.0).pkt_dts }
+    }
+
+    pub fn width(&self) -> c_int {
+        unsafe { (*self.0).width }
+    }
+
+    pub fn height(&self) -> c_int {
+        unsafe { (*self.0).height }
+    }
+
+    /// `AVPixelFormat` for video, `AVSampleFormat` for audio, -1 if unset.
+    pub fn format(&self) -> c_int {
+        unsafe { (*self.0).format }
+    }
+
+    pub fn nb_samples(&self) -> c_int {
+        unsafe { (*self.0).nb_samples }
+    }
+
+    pub fn sample_rate(&self) -> c_int {
+        unsafe { (*self.0).sample_rate }
+    }
+}
+
+impl Drop for Frame {
+    fn drop(&mut self) {
+        unsafe {
+            av_frame_free(&mut self.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_clone_shares_buffers() {
+        let mut frame = Frame::new().unwrap();
+        unsafe {
+            let raw = frame.as_mut_ptr();
+            (*raw).format = AVPixelFormat::AV_PIX_FMT_GRAY8 as c_int;
+            (*raw).width = 16;
+            (*raw).height = 16;
+            (*raw).pts = 42;
+            check(av_frame_get_buffer(raw, 32)).unwrap();
+        }
+
+        let mut clone = frame.try_clone().unwrap();
+        assert_eq!(clone.pts(), 42);
+        assert_eq!((clone.width(), clone.height()), (16, 16));
+        unsafe {
+            assert_eq!((*clone.as_ptr()).data[0], (*frame.as_ptr()).data[0]);
+        }
+
+        // the buffer is shared, so writing to it needs a copy
+        clone.make_writable().unwrap();
+        unsafe {
+            assert!((*clone.as_ptr()).data[0] != (*frame.as_ptr()).data[0]);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::ptr;
+
+use libc::{c_char, c_int};
+use {av_find_info_tag, av_match_name, av_strcasecmp, av_strtok};
+
+use super::FFmpegError;
+
+/// C strings end at the first NUL, so do the same with Rust strings instead
+/// of failing.
+fn c_string(s: &str) -> CString {
+    CString::new(s.split('\0').next().unwrap()).unwrap()
+}
+
+/// Whether `name` appears in the comma-separated list `names`, ignoring
+/// case.
+pub fn match_name(name: &str, names: &str) -> bool {
+    let name = c_string(name);
+    let names = c_string(names);
+
+    unsafe { av_match_name(name.as_ptr(), names.as_ptr()) != 0 }
+}
+
+/// Value of `tag` in a URL-style info string (`"?tag1=val1&tag2=val2"`).
+pub fn find_info_tag(info: &str, tag: &str) -> Result<Option<String>, FFmpegError> {
+    let info = CString::new(info)?;
+    let tag = CString::new(tag)?;
+    // a value can never be longer than the whole info string
+    let mut arg = vec![0 as c_char; info.as_bytes().len() + 1];
+
+    unsafe {
+        if av_find_info_tag(
+            arg.as_mut_ptr(),
+            arg.len() as c_int,
+            tag.as_ptr(),
+            info.as_ptr(),
+        ) == 0
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            CStr::from_ptr(arg.as_ptr()).to_string_lossy().into_owned(),
+        ))
+    }
+}
+
+/// Tokens of a NUL-terminated buffer as split by `av_strtok`, which writes
+/// a NUL after each token.
+pub struct StrTok<'a> {
+    s: *mut c_char,
+    saveptr: *mut c_char,
+    delim: &'a CStr,
+    _buf: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> Iterator for StrTok<'a> {
+    type Item = &'a CStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            let token = av_strtok(self.s, self.delim.as_ptr(), &mut self.saveptr);
+            self.s = ptr::null_mut();
+            if token.is_null() {
+                return None;
+            }
+
+            // tokens never overlap the rest of the buffer av_strtok has
+            // still to look at
+            Some(CStr::from_ptr(token))
+        }
+    }
+}
+
+/// Splits `buf`, which must contain a NUL terminator, at any of the
+/// characters in `delim`, skipping empty tokens.
+pub fn strtok<'a>(buf: &'a mut [u8], delim: &'a CStr) -> StrTok<'a> {
+    assert!(buf.contains(&0), "strtok buffer is not NUL-terminated");
+
+    StrTok {
+        s: buf.as_mut_ptr() as *mut c_char,
+        saveptr: ptr::null_mut(),
+        delim,
+        _buf: PhantomData,
+    }
+}
+
+/// Locale-independent, ASCII case-insensitive comparison.
+pub fn strcasecmp(a: &str, b: &str) -> c_int {
+    let a = c_string(a);
+    let b = c_string(b);
+
+    unsafe { av_strcasecmp(a.as_ptr(), b.as_ptr()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_names() {
+        assert!(match_name("mov", "mov,mp4,m4a"));
+        assert!(match_name("MP4", "mov,mp4,m4a"));
+        assert!(!match_name("mkv", "mov,mp4,m4a"));
+    }
+
+    #[test]
+    fn info_tag() {
+        let info = "?width=320&height=240";
+        assert_eq!(find_info_tag(info, "height").unwrap(), Some("240".into()));
+        assert_eq!(find_info_tag(info, "depth").unwrap(), None);
+    }
+
+    #[test]
+    fn tokens() {
+        let mut buf = *b"a,,b;c\0";
+        let delim = CStr::from_bytes_with_nul(b",;\0").unwrap();
+        let tokens: Vec<_> = strtok(&mut buf, delim)
+            .map(|t| t.to_str().unwrap().to_owned())
+            .collect();
+        assert_eq!(tokens, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn case_insensitive() {
+        assert_eq!(strcasecmp("Matroska", "MATROSKA"), 0);
+        assert!(strcasecmp("a", "B") < 0);
+    }
+}
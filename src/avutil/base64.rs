@@ -0,0 +1,85 @@
+use std::ffi::{CStr, CString};
+
+use libc::{c_char, c_int};
+use {av_base64_decode, av_base64_encode};
+
+use super::error::check;
+use super::FFmpegError;
+
+/// Rust version of the `AV_BASE64_SIZE` macro: the buffer size needed to
+/// encode `len` bytes, including the terminating nul.
+pub const fn base64_size(len: usize) -> usize {
+    4 * len.div_ceil(3) + 1
+}
+
+pub fn base64_encode(data: &[u8]) -> String {
+    let mut buf = vec![0 as c_char; base64_size(data.len())];
+
+    unsafe {
+        let ret = av_base64_encode(
+            buf.as_mut_ptr(),
+            buf.len() as c_int,
+            data.as_ptr(),
+            data.len() as c_int,
+        );
+        assert!(!ret.is_null(), "input too large for av_base64_encode");
+
+        // base64 output is always ASCII
+        CStr::from_ptr(ret).to_string_lossy().into_owned()
+    }
+}
+
+pub fn base64_decode(s: &str) -> Result<Vec<u8>, FFmpegError> {
+    let input = CString::new(s)?;
+    let mut buf = vec![0u8; 3 * s.len() / 4 + 3];
+
+    unsafe {
+        let len = check(av_base64_decode(
+            buf.as_mut_ptr(),
+            input.as_ptr(),
+            buf.len() as c_int,
+        ))?;
+        buf.truncate(len as usize);
+    }
+
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4648, section 10
+    #[test]
+    fn rfc4648() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+        ];
+
+        for &(data, encoded) in &vectors {
+            assert_eq!(base64_encode(data), encoded);
+            assert_eq!(base64_decode(encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let data: Vec<u8> = (0..=255).collect();
+        for len in 0..data.len() {
+            let encoded = base64_encode(&data[..len]);
+            assert_eq!(encoded.len() + 1, base64_size(len));
+            assert_eq!(base64_decode(&encoded).unwrap(), &data[..len]);
+        }
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert!(base64_decode("Zm9v!").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::mem;
+
+use libc::{c_char, c_int};
+use {
+    av_channel_layout_compare, av_channel_layout_copy, av_channel_layout_default,
+    av_channel_layout_describe, av_channel_layout_from_mask, av_channel_layout_from_string,
+    av_channel_layout_uninit, AVChannelLayout,
+};
+
+use super::error::check;
+use super::FFmpegError;
+
+/// Owned `AVChannelLayout`, uninitialized with `av_channel_layout_uninit` on drop.
+pub struct ChannelLayout(AVChannelLayout);
+
+// SAFETY: AVChannelLayout is plain data plus, for custom orders, an owned
+// channel map; &self methods only read it. See the safety_notes module.
+unsafe impl Send for ChannelLayout {}
+unsafe impl Sync for ChannelLayout {}
+
+impl ChannelLayout {
+    /// Default layout for the given number of channels, as chosen by
+    /// `av_channel_layout_default`.
+    pub fn with_channels(nb_channels: c_int) -> Self {
+        unsafe {
+            let mut layout = ChannelLayout(mem::zeroed());
+            av_channel_layout_default(&mut layout.0, nb_channels);
+            layout
+        }
+    }
+
+    pub fn nb_channels(&self) -> c_int {
+        self.0.nb_channels
+    }
+
+    pub fn as_ptr(&self) -> *const AVChannelLayout {
+        &self.0
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut AVChannelLayout {
+        &mut self.0
+    }
+}
+
+impl Drop for ChannelLayout {
+    fn drop(&mut self) {
+        unsafe {
+            av_channel_layout_uninit(&mut self.0);
+        }
+    }
+}
+
+impl Default for ChannelLayout {
+    fn default() -> Self {
+        ChannelLayout::with_channels(2)
+    }
+}
+
+impl Clone for ChannelLayout {
+    fn clone(&self) -> Self {
+        unsafe {
+            let mut layout = ChannelLayout(mem::zeroed());
+            // only fails when allocating the map of a custom layout
+            if av_channel_layout_copy(&mut layout.0, &self.0) < 0 {
+                panic!("av_channel_layout_copy failed");
+            }
+            layout
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for ChannelLayout {
+    type Error = FFmpegError;
+
+    fn try_from(description: &'a str) -> Result<Self, Self::Error> {
+        let description = CString::new(description)?;
+
+        unsafe {
+            let mut layout = ChannelLayout(mem::zeroed());
+            check(av_channel_layout_from_string(
+                &mut layout.0,
+                description.as_ptr(),
+            ))?;
+            Ok(layout)
+        }
+    }
+}
+
+impl TryFrom<u64> for ChannelLayout {
+    type Error = FFmpegError;
+
+    fn try_from(mask: u64) -> Result<Self, Self::Error> {
+        unsafe {
+            let mut layout = ChannelLayout(mem::zeroed());
+            check(av_channel_layout_from_mask(&mut layout.0, mask))?;
+            Ok(layout)
+        }
+    }
+}
+
+impl fmt::Display for ChannelLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = vec![0 as c_char; 64];
+
+        unsafe {
+            // av_channel_layout_describe returns the size it would have
+            // needed, so retry once with a large enough buffer.
+            let mut ret = av_channel_layout_describe(&self.0, buf.as_mut_ptr(), buf.len());
+            if ret > buf.len() as c_int {
+                buf.resize(ret as usize, 0);
+                ret = av_channel_layout_describe(&self.0, buf.as_mut_ptr(), buf.len());
+            }
+            if ret < 0 {
+                return Err(fmt::Error);
+            }
+
+            f.write_str(&CStr::from_ptr(buf.as_ptr()).to_string_lossy())
+        }
+    }
+}
+
+impl fmt::Debug for ChannelLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ChannelLayout")
+            .field(&self.to_string())
+            .finish()
+    }
+}
+
+impl PartialEq for ChannelLayout {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { av_channel_layout_compare(&self.0, &other.0) == 0 }
+    }
+}
@@ -0,0 +1,122 @@
+use std::mem;
+
+use libc::{c_int, c_void};
+use {
+    av_crc, av_crc_get_table, av_crc_init, av_lzo1x_decode, AVCRCId, AVCRC, AV_LZO_INPUT_PADDING,
+    AV_LZO_OUTPUT_FULL, AV_LZO_OUTPUT_PADDING,
+};
+
+use super::error::check;
+use super::{FFmpegError, AVERROR_BUFFER_TOO_SMALL, AVERROR_INVALIDDATA};
+
+/// Decodes LZO1X compressed `src` into `out`, storing the number of decoded
+/// bytes in `dst_len`.
+///
+/// `av_lzo1x_decode` may write past the requested output size, so the last
+/// `AV_LZO_OUTPUT_PADDING` bytes of `out` are only used as scratch space. The
+/// input is copied into a padded buffer for the same reason.
+pub fn lzo1x_decode(out: &mut [u8], dst_len: &mut usize, src: &[u8]) -> Result<(), FFmpegError> {
+    let capacity = out.len().saturating_sub(AV_LZO_OUTPUT_PADDING as usize);
+    let mut input = Vec::with_capacity(src.len() + AV_LZO_INPUT_PADDING as usize);
+    input.extend_from_slice(src);
+    input.resize(src.len() + AV_LZO_INPUT_PADDING as usize, 0);
+
+    let mut outlen = capacity as c_int;
+    let mut inlen = src.len() as c_int;
+    let ret = unsafe {
+        av_lzo1x_decode(
+            out.as_mut_ptr() as *mut c_void,
+            &mut outlen,
+            input.as_ptr() as *const c_void,
+            &mut inlen,
+        )
+    };
+
+    // outlen holds the number of output bytes left unused
+    *dst_len = capacity - outlen as usize;
+
+    match ret {
+        0 => Ok(()),
+        r if r & AV_LZO_OUTPUT_FULL != 0 => Err(FFmpegError::Other(AVERROR_BUFFER_TOO_SMALL)),
+        _ => Err(FFmpegError::Other(AVERROR_INVALIDDATA)),
+    }
+}
+
+/// Initializes a CRC table in `ctx` for a `bits` wide CRC with the given
+/// polynomial; `ctx` needs room for 257 entries, or 1024 for the faster
+/// non-`CONFIG_SMALL` tables.
+pub fn crc_init(le: bool, bits: u32, poly: u32, ctx: &mut [AVCRC]) -> Result<(), FFmpegError> {
+    unsafe {
+        check(av_crc_init(
+            ctx.as_mut_ptr(),
+            le as c_int,
+            bits as c_int,
+            poly,
+            mem::size_of_val(ctx) as c_int,
+        ))?;
+    }
+
+    Ok(())
+}
+
+pub unsafe fn crc(ctx: *const AVCRC, crc: u32, buf: &[u8]) -> u32 {
+    av_crc(ctx, crc, buf.as_ptr(), buf.len())
+}
+
+/// CRC-32 as used by MPEG-2 transport streams (big endian IEEE polynomial,
+/// initial value `0xFFFFFFFF`, no final XOR).
+pub fn crc32_mpeg2(buf: &[u8]) -> u32 {
+    // big endian tables hold byte swapped entries, so av_crc returns the
+    // result byte swapped as well
+    unsafe { crc(av_crc_get_table(AVCRCId::AV_CRC_32_IEEE), u32::MAX, buf).swap_bytes() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_check_values() {
+        assert_eq!(crc32_mpeg2(b"123456789"), 0x0376_E6E7);
+
+        let ieee = unsafe {
+            !crc(
+                av_crc_get_table(AVCRCId::AV_CRC_32_IEEE_LE),
+                u32::MAX,
+                b"123456789",
+            )
+        };
+        assert_eq!(ieee, 0xCBF4_3926);
+    }
+
+    #[test]
+    fn custom_table() {
+        let mut table = [0 as AVCRC; 1024];
+        crc_init(false, 32, 0x04C1_1DB7, &mut table).unwrap();
+
+        let sum = unsafe { crc(table.as_ptr(), u32::MAX, b"123456789") };
+        assert_eq!(sum.swap_bytes(), crc32_mpeg2(b"123456789"));
+    }
+
+    // five literal bytes followed by the end of stream marker
+    const HELLO: &[u8] = &[22, b'h', b'e', b'l', b'l', b'o', 0x11, 0x00, 0x00];
+
+    #[test]
+    fn lzo_literals() {
+        let mut out = [0u8; 5 + AV_LZO_OUTPUT_PADDING as usize];
+        let mut len = 0;
+        lzo1x_decode(&mut out, &mut len, HELLO).unwrap();
+        assert_eq!(&out[..len], b"hello");
+    }
+
+    #[test]
+    fn lzo_output_full() {
+        let mut out = [0u8; 3 + AV_LZO_OUTPUT_PADDING as usize];
+        let mut len = 0;
+        assert_eq!(
+            lzo1x_decode(&mut out, &mut len, HELLO),
+            Err(FFmpegError::Other(AVERROR_BUFFER_TOO_SMALL))
+        );
+        assert_eq!(&out[..len], b"hel");
+    }
+}
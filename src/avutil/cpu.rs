@@ -0,0 +1,128 @@
+use libc::c_int;
+use {av_cpu_count, av_force_cpu_flags, av_get_cpu_flags};
+
+#[cfg(feature = "ffmpeg_4_0")]
+use av_cpu_max_align;
+
+#[cfg(target_arch = "aarch64")]
+use AV_CPU_FLAG_ARMV8;
+#[cfg(all(
+    feature = "ffmpeg_4_1",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+use AV_CPU_FLAG_AVX512;
+#[cfg(any(target_arch = "arm", target_arch = "aarch64"))]
+use AV_CPU_FLAG_NEON;
+#[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+use {AV_CPU_FLAG_ALTIVEC, AV_CPU_FLAG_VSX};
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use {
+    AV_CPU_FLAG_AVX, AV_CPU_FLAG_AVX2, AV_CPU_FLAG_FMA3, AV_CPU_FLAG_MMX, AV_CPU_FLAG_SSE,
+    AV_CPU_FLAG_SSE2, AV_CPU_FLAG_SSE3, AV_CPU_FLAG_SSE4, AV_CPU_FLAG_SSE42, AV_CPU_FLAG_SSSE3,
+};
+
+bitflags! {
+    /// `AV_CPU_FLAG_*` values as returned by `av_get_cpu_flags`.
+    ///
+    /// The flag bits overlap between architectures (e.g. `AV_CPU_FLAG_NEON`
+    /// has the same value as `AV_CPU_FLAG_SSSE3`), so each flag is only
+    /// defined when targeting the architecture it belongs to.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    pub struct CpuFlags: u32 {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        const X86_MMX = AV_CPU_FLAG_MMX as u32;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        const X86_SSE = AV_CPU_FLAG_SSE as u32;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        const X86_SSE2 = AV_CPU_FLAG_SSE2 as u32;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        const X86_SSE3 = AV_CPU_FLAG_SSE3 as u32;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        const X86_SSSE3 = AV_CPU_FLAG_SSSE3 as u32;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        const X86_SSE4 = AV_CPU_FLAG_SSE4 as u32;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        const X86_SSE42 = AV_CPU_FLAG_SSE42 as u32;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        const X86_AVX = AV_CPU_FLAG_AVX as u32;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        const X86_FMA3 = AV_CPU_FLAG_FMA3 as u32;
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        const X86_AVX2 = AV_CPU_FLAG_AVX2 as u32;
+        #[cfg(all(feature = "ffmpeg_4_1", any(target_arch = "x86", target_arch = "x86_64")))]
+        const X86_AVX512 = AV_CPU_FLAG_AVX512 as u32;
+        #[cfg(target_arch = "arm")]
+        const ARM_NEON = AV_CPU_FLAG_NEON as u32;
+        #[cfg(target_arch = "aarch64")]
+        const AARCH64_NEON = AV_CPU_FLAG_NEON as u32;
+        #[cfg(target_arch = "aarch64")]
+        const AARCH64_ARMV8 = AV_CPU_FLAG_ARMV8 as u32;
+        #[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+        const PPC_ALTIVEC = AV_CPU_FLAG_ALTIVEC as u32;
+        #[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+        const PPC_VSX = AV_CPU_FLAG_VSX as u32;
+    }
+}
+
+pub fn cpu_flags() -> u32 {
+    unsafe { av_get_cpu_flags() as u32 }
+}
+
+/// Overrides the detected CPU flags. Passing `u32::MAX` (i.e. `-1`) restores
+/// runtime detection, see `reset_cpu_flags`.
+pub fn force_cpu_flags(flags: u32) {
+    unsafe { av_force_cpu_flags(flags as c_int) }
+}
+
+/// Undoes `force_cpu_flags` and `override_cpu_flags`, going back to runtime
+/// detection.
+pub fn reset_cpu_flags() {
+    force_cpu_flags(u32::MAX)
+}
+
+/// `cpu_flags` as `CpuFlags`, bits of flags not listed there are kept.
+pub fn detected_cpu_flags() -> CpuFlags {
+    CpuFlags::from_bits_retain(cpu_flags())
+}
+
+/// `force_cpu_flags` taking `CpuFlags`; use `reset_cpu_flags` to go back to
+/// runtime detection.
+pub fn override_cpu_flags(flags: CpuFlags) {
+    force_cpu_flags(flags.bits())
+}
+
+pub fn cpu_count() -> c_int {
+    unsafe { av_cpu_count() }
+}
+
+#[cfg(feature = "ffmpeg_4_0")]
+pub fn cpu_max_align() -> usize {
+    unsafe { av_cpu_max_align() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count() {
+        assert!(cpu_count() > 0);
+    }
+
+    #[test]
+    fn override_and_reset() {
+        override_cpu_flags(CpuFlags::empty());
+        assert_eq!(cpu_flags(), 0);
+
+        reset_cpu_flags();
+        #[cfg(target_arch = "x86_64")]
+        assert!(detected_cpu_flags().contains(CpuFlags::X86_SSE2));
+    }
+
+    #[cfg(feature = "ffmpeg_4_0")]
+    #[test]
+    fn max_align() {
+        let align = cpu_max_align();
+        assert!(align >= 8 && align.is_power_of_two());
+    }
+}
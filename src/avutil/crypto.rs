@@ -0,0 +1,333 @@
+use std::ptr;
+
+use libc::{c_int, c_void, EINVAL, ENOMEM};
+use {
+    av_aes_alloc, av_aes_crypt, av_aes_init, av_blowfish_alloc, av_blowfish_crypt,
+    av_blowfish_crypt_ecb, av_blowfish_init, av_camellia_alloc, av_camellia_crypt,
+    av_camellia_init, av_cast5_alloc, av_cast5_crypt2, av_cast5_init, av_free, av_twofish_alloc,
+    av_twofish_crypt, av_twofish_init, av_xtea_alloc, av_xtea_crypt, av_xtea_init, av_xtea_le_init,
+    AVBlowfish, AVXTEA,
+};
+
+use super::error::check;
+use super::{FFmpegError, AVERROR};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CipherType {
+    Aes,
+    Blowfish,
+    Camellia,
+    Cast5,
+    Twofish,
+}
+
+impl CipherType {
+    pub fn block_size(self) -> usize {
+        match self {
+            CipherType::Aes | CipherType::Camellia | CipherType::Twofish => 16,
+            CipherType::Blowfish | CipherType::Cast5 => 8,
+        }
+    }
+}
+
+/// Block cipher context for any of the ciphers in libavutil, set up for
+/// encryption. Passing an IV selects CBC mode, otherwise ECB is used.
+pub struct BlockCipher(*mut c_void, CipherType);
+
+// SAFETY: the cipher contexts have no thread affinity and are only mutated
+// through &mut self; see the safety_notes module.
+unsafe impl Send for BlockCipher {}
+
+impl BlockCipher {
+    pub fn new_aes(key: &[u8]) -> Result<Self, FFmpegError> {
+        unsafe {
+            let cipher = BlockCipher::alloc(av_aes_alloc() as *mut c_void, CipherType::Aes)?;
+            check(av_aes_init(
+                cipher.0 as *mut _,
+                key.as_ptr(),
+                (key.len() * 8) as c_int,
+                0,
+            ))?;
+            Ok(cipher)
+        }
+    }
+
+    pub fn new_blowfish(key: &[u8]) -> Result<Self, FFmpegError> {
+        check_blowfish_key(key)?;
+
+        unsafe {
+            let cipher =
+                BlockCipher::alloc(av_blowfish_alloc() as *mut c_void, CipherType::Blowfish)?;
+            av_blowfish_init(cipher.0 as *mut _, key.as_ptr(), key.len() as c_int);
+            Ok(cipher)
+        }
+    }
+
+    pub fn new_camellia(key: &[u8]) -> Result<Self, FFmpegError> {
+        unsafe {
+            let cipher =
+                BlockCipher::alloc(av_camellia_alloc() as *mut c_void, CipherType::Camellia)?;
+            check(av_camellia_init(
+                cipher.0 as *mut _,
+                key.as_ptr(),
+                (key.len() * 8) as c_int,
+            ))?;
+            Ok(cipher)
+        }
+    }
+
+    pub fn new_cast5(key: &[u8]) -> Result<Self, FFmpegError> {
+        unsafe {
+            let cipher = BlockCipher::alloc(av_cast5_alloc() as *mut c_void, CipherType::Cast5)?;
+            check(av_cast5_init(
+                cipher.0 as *mut _,
+                key.as_ptr(),
+                (key.len() * 8) as c_int,
+            ))?;
+            Ok(cipher)
+        }
+    }
+
+    pub fn new_twofish(key: &[u8]) -> Result<Self, FFmpegError> {
+        unsafe {
+            let cipher =
+                BlockCipher::alloc(av_twofish_alloc() as *mut c_void, CipherType::Twofish)?;
+            check(av_twofish_init(
+                cipher.0 as *mut _,
+                key.as_ptr(),
+                (key.len() * 8) as c_int,
+            ))?;
+            Ok(cipher)
+        }
+    }
+
+    fn alloc(ctx: *mut c_void, cipher_type: CipherType) -> Result<Self, FFmpegError> {
+        if ctx.is_null() {
+            Err(FFmpegError::Other(AVERROR(ENOMEM)))
+        } else {
+            Ok(BlockCipher(ctx, cipher_type))
+        }
+    }
+
+    pub fn cipher_type(&self) -> CipherType {
+        self.1
+    }
+
+    /// Encrypts `src` into `dst`. `src` must be a whole number of blocks, and
+    /// `iv`, if given, exactly one block; it is updated in place so that
+    /// consecutive calls chain.
+    pub fn encrypt(
+        &mut self,
+        dst: &mut [u8],
+        src: &[u8],
+        iv: Option<&mut [u8]>,
+    ) -> Result<(), FFmpegError> {
+        let count = block_count(dst, src, &iv, self.1.block_size())?;
+        let iv = iv.map_or(ptr::null_mut(), |iv| iv.as_mut_ptr());
+        let (ctx, dst, src) = (self.0, dst.as_mut_ptr(), src.as_ptr());
+
+        unsafe {
+            match self.1 {
+                CipherType::Aes => av_aes_crypt(ctx as *mut _, dst, src, count, iv, 0),
+                CipherType::Blowfish => av_blowfish_crypt(ctx as *mut _, dst, src, count, iv, 0),
+                CipherType::Camellia => av_camellia_crypt(ctx as *mut _, dst, src, count, iv, 0),
+                CipherType::Cast5 => av_cast5_crypt2(ctx as *mut _, dst, src, count, iv, 0),
+                CipherType::Twofish => av_twofish_crypt(ctx as *mut _, dst, src, count, iv, 0),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for BlockCipher {
+    fn drop(&mut self) {
+        // all of the contexts are plain av_malloc'ed structs
+        unsafe {
+            av_free(self.0);
+        }
+    }
+}
+
+/// av_blowfish_init has no way to report errors and reads the key cyclically,
+/// so it must not be handed an empty (or overlong) key.
+fn check_blowfish_key(key: &[u8]) -> Result<(), FFmpegError> {
+    if (4..=56).contains(&key.len()) {
+        Ok(())
+    } else {
+        Err(FFmpegError::Other(AVERROR(EINVAL)))
+    }
+}
+
+/// Checks the buffer sizes for a block cipher call and returns the number of
+/// blocks in `src`.
+fn block_count(
+    dst: &[u8],
+    src: &[u8],
+    iv: &Option<&mut [u8]>,
+    block_size: usize,
+) -> Result<c_int, FFmpegError> {
+    if !src.len().is_multiple_of(block_size)
+        || dst.len() < src.len()
+        || iv.as_ref().is_some_and(|iv| iv.len() != block_size)
+    {
+        return Err(FFmpegError::Other(AVERROR(EINVAL)));
+    }
+
+    Ok((src.len() / block_size) as c_int)
+}
+
+/// Blowfish context, for when decryption or raw ECB block operations are
+/// needed on top of what `BlockCipher` provides.
+pub struct Blowfish(*mut AVBlowfish);
+
+// SAFETY: as for BlockCipher.
+unsafe impl Send for Blowfish {}
+
+impl Blowfish {
+    pub fn new(key: &[u8]) -> Result<Self, FFmpegError> {
+        check_blowfish_key(key)?;
+
+        unsafe {
+            let ctx = av_blowfish_alloc();
+            if ctx.is_null() {
+                return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+            }
+            av_blowfish_init(ctx, key.as_ptr(), key.len() as c_int);
+            Ok(Blowfish(ctx))
+        }
+    }
+
+    /// Encrypts or decrypts a single block given as its two 32-bit halves.
+    pub fn crypt_ecb(&mut self, mut xl: u32, mut xr: u32, decrypt: bool) -> (u32, u32) {
+        unsafe {
+            av_blowfish_crypt_ecb(self.0, &mut xl, &mut xr, decrypt as c_int);
+        }
+        (xl, xr)
+    }
+
+    /// Same requirements as `BlockCipher::encrypt`, with a block size of 8.
+    pub fn crypt(
+        &mut self,
+        dst: &mut [u8],
+        src: &[u8],
+        iv: Option<&mut [u8]>,
+        decrypt: bool,
+    ) -> Result<(), FFmpegError> {
+        let count = block_count(dst, src, &iv, 8)?;
+        let iv = iv.map_or(ptr::null_mut(), |iv| iv.as_mut_ptr());
+
+        unsafe {
+            av_blowfish_crypt(
+                self.0,
+                dst.as_mut_ptr(),
+                src.as_ptr(),
+                count,
+                iv,
+                decrypt as c_int,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Blowfish {
+    fn drop(&mut self) {
+        unsafe {
+            av_free(self.0 as *mut c_void);
+        }
+    }
+}
+
+/// XTEA context.
+pub struct Xtea(*mut AVXTEA);
+
+// SAFETY: as for BlockCipher.
+unsafe impl Send for Xtea {}
+
+impl Xtea {
+    /// Key in big-endian word order, as in the reference implementation.
+    pub fn new(key: &[u8; 16]) -> Result<Self, FFmpegError> {
+        unsafe {
+            let xtea = Xtea::alloc()?;
+            av_xtea_init(xtea.0, key.as_ptr());
+            Ok(xtea)
+        }
+    }
+
+    /// Key in little-endian word order.
+    pub fn new_le(key: &[u8; 16]) -> Result<Self, FFmpegError> {
+        unsafe {
+            let xtea = Xtea::alloc()?;
+            av_xtea_le_init(xtea.0, key.as_ptr());
+            Ok(xtea)
+        }
+    }
+
+    unsafe fn alloc() -> Result<Self, FFmpegError> {
+        let ctx = av_xtea_alloc();
+        if ctx.is_null() {
+            Err(FFmpegError::Other(AVERROR(ENOMEM)))
+        } else {
+            Ok(Xtea(ctx))
+        }
+    }
+
+    /// Same requirements as `BlockCipher::encrypt`, with a block size of 8.
+    pub fn crypt(
+        &mut self,
+        dst: &mut [u8],
+        src: &[u8],
+        iv: Option<&mut [u8]>,
+        decrypt: bool,
+    ) -> Result<(), FFmpegError> {
+        let count = block_count(dst, src, &iv, 8)?;
+        let iv = iv.map_or(ptr::null_mut(), |iv| iv.as_mut_ptr());
+
+        unsafe {
+            av_xtea_crypt(
+                self.0,
+                dst.as_mut_ptr(),
+                src.as_ptr(),
+                count,
+                iv,
+                decrypt as c_int,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Xtea {
+    fn drop(&mut self) {
+        unsafe {
+            av_free(self.0 as *mut c_void);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blowfish_rejects_bad_keys() {
+        let einval = FFmpegError::Other(AVERROR(EINVAL));
+
+        assert_eq!(Blowfish::new(&[]).err(), Some(einval));
+        assert_eq!(Blowfish::new(&[0; 57]).err(), Some(einval));
+        assert_eq!(BlockCipher::new_blowfish(&[]).err(), Some(einval));
+    }
+
+    #[test]
+    fn blowfish_ecb() {
+        // first vector of Eric Young's Blowfish test set
+        let mut blowfish = Blowfish::new(&[0; 8]).unwrap();
+
+        let (xl, xr) = blowfish.crypt_ecb(0, 0, false);
+        assert_eq!((xl, xr), (0x4EF9_9745, 0x6198_DD78));
+        assert_eq!(blowfish.crypt_ecb(xl, xr, true), (0, 0));
+    }
+}
@@ -0,0 +1,47 @@
+use std::ffi::CString;
+
+use libc::c_int;
+use {av_dict_copy, av_dict_count, av_dict_parse_string, AVDictionary};
+
+use super::error::check;
+use super::FFmpegError;
+
+// The AV_DICT_* flags are already bound as c_int by the int_macro callback in
+// build.rs, so they are not redefined here.
+
+/// Parses `"key=val:key2=val2"` style strings into `dict`, allocating it if
+/// null. Entries parsed before a failure are kept in `dict`.
+pub unsafe fn dict_parse_string(
+    dict: &mut *mut AVDictionary,
+    str: &str,
+    key_val_sep: &str,
+    pairs_sep: &str,
+    flags: c_int,
+) -> Result<(), FFmpegError> {
+    let str = CString::new(str)?;
+    let key_val_sep = CString::new(key_val_sep)?;
+    let pairs_sep = CString::new(pairs_sep)?;
+
+    check(av_dict_parse_string(
+        dict,
+        str.as_ptr(),
+        key_val_sep.as_ptr(),
+        pairs_sep.as_ptr(),
+        flags,
+    ))?;
+    Ok(())
+}
+
+/// Copies all entries of `src` into `dst`, allocating it if null.
+pub unsafe fn dict_copy(
+    dst: &mut *mut AVDictionary,
+    src: *const AVDictionary,
+    flags: c_int,
+) -> Result<(), FFmpegError> {
+    check(av_dict_copy(dst, src, flags))?;
+    Ok(())
+}
+
+pub unsafe fn dict_count(dict: *const AVDictionary) -> c_int {
+    av_dict_count(dict)
+}
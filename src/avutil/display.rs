@@ -0,0 +1,72 @@
+use std::mem;
+
+use libc::c_int;
+use {
+    av_display_matrix_flip, av_display_rotation_get, av_display_rotation_set,
+    av_frame_get_side_data, AVFrame, AVFrameSideDataType,
+};
+
+/// Counterclockwise rotation in degrees, in the range [-180, 180], described
+/// by a display matrix; NaN if the matrix is singular.
+pub fn rotation_get(matrix: &[i32; 9]) -> f64 {
+    unsafe { av_display_rotation_get(matrix.as_ptr()) }
+}
+
+/// Overwrites `matrix` with a pure clockwise rotation by `angle` degrees, so
+/// `rotation_get` returns `-angle` for it.
+pub fn rotation_set(matrix: &mut [i32; 9], angle: f64) {
+    unsafe { av_display_rotation_set(matrix.as_mut_ptr(), angle) }
+}
+
+pub fn matrix_flip(matrix: &mut [i32; 9], hflip: bool, vflip: bool) {
+    unsafe { av_display_matrix_flip(matrix.as_mut_ptr(), hflip as c_int, vflip as c_int) }
+}
+
+/// Side data of the given type attached to `frame`, reinterpreted as `T` if
+/// it is large enough.
+pub(crate) unsafe fn frame_side_data<'a, T>(
+    frame: *const AVFrame,
+    type_: AVFrameSideDataType,
+) -> Option<&'a T> {
+    let side_data = av_frame_get_side_data(frame, type_);
+    if side_data.is_null() || (*side_data).size < mem::size_of::<T>() as _ {
+        return None;
+    }
+
+    Some(&*((*side_data).data as *const T))
+}
+
+/// The `AV_FRAME_DATA_DISPLAYMATRIX` side data of `frame`, if present; it is
+/// only valid for as long as the side data is attached to the frame.
+pub unsafe fn display_matrix_from_frame<'a>(frame: *const AVFrame) -> Option<&'a [i32; 9]> {
+    frame_side_data(frame, AVFrameSideDataType::AV_FRAME_DATA_DISPLAYMATRIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_round_trip() {
+        let mut matrix = [0; 9];
+        rotation_set(&mut matrix, 90.0);
+        assert_eq!(matrix, [0, 1 << 16, 0, -(1 << 16), 0, 0, 0, 0, 1 << 30]);
+        assert!((rotation_get(&matrix) + 90.0).abs() < 1e-9);
+
+        for &angle in &[0.0, 45.0, -90.0, 135.0] {
+            rotation_set(&mut matrix, angle);
+            assert!((rotation_get(&matrix) + angle).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn flip_and_singular() {
+        let mut matrix = [0; 9];
+        rotation_set(&mut matrix, 0.0);
+        matrix_flip(&mut matrix, true, false);
+        assert_eq!(matrix[0], -(1 << 16));
+        assert_eq!(matrix[4], 1 << 16);
+
+        assert!(rotation_get(&[0; 9]).is_nan());
+    }
+}
@@ -1,4 +1,18 @@
-use libc::{c_char, c_int, size_t};
+use std::error;
+use std::ffi::{CStr, NulError};
+use std::fmt;
+
+use libc::{
+    c_char, c_int, size_t, E2BIG, EACCES, EADDRINUSE, EADDRNOTAVAIL, EAFNOSUPPORT, EAGAIN,
+    EALREADY, EBADF, EBADMSG, EBUSY, ECANCELED, ECHILD, ECONNABORTED, ECONNREFUSED, ECONNRESET,
+    EDEADLK, EDESTADDRREQ, EDOM, EEXIST, EFAULT, EFBIG, EHOSTUNREACH, EIDRM, EILSEQ, EINPROGRESS,
+    EINTR, EINVAL, EIO, EISCONN, EISDIR, ELOOP, EMFILE, EMLINK, EMSGSIZE, ENAMETOOLONG, ENETDOWN,
+    ENETRESET, ENETUNREACH, ENFILE, ENOBUFS, ENODEV, ENOENT, ENOEXEC, ENOLCK, ENOMEM, ENOMSG,
+    ENOPROTOOPT, ENOSPC, ENOSYS, ENOTCONN, ENOTDIR, ENOTEMPTY, ENOTSOCK, ENOTSUP, ENOTTY, ENXIO,
+    EOPNOTSUPP, EOVERFLOW, EPERM, EPIPE, EPROTO, EPROTONOSUPPORT, EPROTOTYPE, ERANGE, EROFS,
+    ESPIPE, ESRCH, ETIMEDOUT, ETXTBSY, EWOULDBLOCK, EXDEV,
+};
+use AV_ERROR_MAX_STRING_SIZE;
 
 // Note: FFmpeg's AVERROR and AVUNERROR are conditionally defined based on
 // whether EDOM is positive, claiming that "Some platforms have E* and errno
@@ -18,6 +32,105 @@ pub const fn AVUNERROR(e: c_int) -> c_int {
     -e
 }
 
+// POSIX errnos that FFmpeg passes through AVERROR(), with the platform's
+// values; on Windows libc mirrors the CRT's, which is what FFmpeg uses there.
+const POSIX_ERRNOS: &[c_int] = &[
+    E2BIG,
+    EACCES,
+    EADDRINUSE,
+    EADDRNOTAVAIL,
+    EAFNOSUPPORT,
+    EAGAIN,
+    EALREADY,
+    EBADF,
+    EBADMSG,
+    EBUSY,
+    ECANCELED,
+    ECHILD,
+    ECONNABORTED,
+    ECONNREFUSED,
+    ECONNRESET,
+    EDEADLK,
+    EDESTADDRREQ,
+    EDOM,
+    EEXIST,
+    EFAULT,
+    EFBIG,
+    EHOSTUNREACH,
+    EIDRM,
+    EILSEQ,
+    EINPROGRESS,
+    EINTR,
+    EINVAL,
+    EIO,
+    EISCONN,
+    EISDIR,
+    ELOOP,
+    EMFILE,
+    EMLINK,
+    EMSGSIZE,
+    ENAMETOOLONG,
+    ENETDOWN,
+    ENETRESET,
+    ENETUNREACH,
+    ENFILE,
+    ENOBUFS,
+    ENODEV,
+    ENOENT,
+    ENOEXEC,
+    ENOLCK,
+    ENOMEM,
+    ENOMSG,
+    ENOPROTOOPT,
+    ENOSPC,
+    ENOSYS,
+    ENOTCONN,
+    ENOTDIR,
+    ENOTEMPTY,
+    ENOTSOCK,
+    ENOTSUP,
+    ENOTTY,
+    ENXIO,
+    EOPNOTSUPP,
+    EOVERFLOW,
+    EPERM,
+    EPIPE,
+    EPROTO,
+    EPROTONOSUPPORT,
+    EPROTOTYPE,
+    ERANGE,
+    EROFS,
+    ESPIPE,
+    ESRCH,
+    ETIMEDOUT,
+    ETXTBSY,
+    EWOULDBLOCK,
+    EXDEV,
+];
+
+/// Same as `AVERROR`; FFmpeg has no translation table, every errno is
+/// negated as is.
+#[inline]
+pub fn posix_to_averror(errno: c_int) -> c_int {
+    AVERROR(errno)
+}
+
+/// The POSIX errno wrapped in `e`, or `None` for FFmpeg's own `AVERROR_*`
+/// codes and anything else that is not a known errno.
+pub fn averror_to_posix(e: c_int) -> Option<c_int> {
+    // AVUNERROR would overflow on c_int::MIN
+    if e >= 0 || e == c_int::MIN {
+        return None;
+    }
+
+    let errno = AVUNERROR(e);
+    if POSIX_ERRNOS.contains(&errno) {
+        Some(errno)
+    } else {
+        None
+    }
+}
+
 macro_rules! FFERRTAG {
     ($a:expr, $b:expr, $c:expr, $d:expr) => {
         -MKTAG!($a, $b, $c, $d) as c_int
@@ -67,3 +180,119 @@ pub unsafe fn av_make_error_string(
 extern "C" {
     pub fn av_strerror(errnum: c_int, errbuf: *mut c_char, errbuf_size: size_t) -> c_int;
 }
+
+/// Error returned by the safe wrappers in this crate.
+///
+/// `AVERROR(EAGAIN)` and `AVERROR_EOF` get their own variants since they are
+/// part of the normal control flow of the send/receive style APIs; every
+/// other negative return code is kept as is.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FFmpegError {
+    Again,
+    Eof,
+    Other(c_int),
+}
+
+impl FFmpegError {
+    pub fn from_code(code: c_int) -> Self {
+        match code {
+            c if c == AVERROR(EAGAIN) => FFmpegError::Again,
+            AVERROR_EOF => FFmpegError::Eof,
+            c => FFmpegError::Other(c),
+        }
+    }
+
+    pub fn code(self) -> c_int {
+        match self {
+            FFmpegError::Again => AVERROR(EAGAIN),
+            FFmpegError::Eof => AVERROR_EOF,
+            FFmpegError::Other(c) => c,
+        }
+    }
+}
+
+impl From<FFmpegError> for c_int {
+    fn from(e: FFmpegError) -> c_int {
+        e.code()
+    }
+}
+
+// Strings handed to FFmpeg cannot contain interior nul bytes.
+impl From<NulError> for FFmpegError {
+    fn from(_: NulError) -> FFmpegError {
+        FFmpegError::Other(AVERROR(EINVAL))
+    }
+}
+
+impl fmt::Display for FFmpegError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = [0 as c_char; AV_ERROR_MAX_STRING_SIZE];
+
+        unsafe {
+            if av_strerror(self.code(), buf.as_mut_ptr(), buf.len()) < 0 {
+                return write!(f, "Unknown error {}", self.code());
+            }
+
+            f.write_str(&CStr::from_ptr(buf.as_ptr()).to_string_lossy())
+        }
+    }
+}
+
+impl error::Error for FFmpegError {}
+
+/// Description of an error code, `"Unknown error <errnum>"` if FFmpeg has
+/// none. Same as formatting the corresponding `FFmpegError`.
+pub fn strerror(errnum: c_int) -> String {
+    FFmpegError::from_code(errnum).to_string()
+}
+
+/// Counterpart of the `av_err2str` macro, which bindgen cannot translate;
+/// an alias of `strerror`.
+#[inline]
+pub fn err2str(errnum: c_int) -> String {
+    strerror(errnum)
+}
+
+// Maps a negative return code to an error, passing non-negative values
+// (often a count or a size) through.
+#[inline]
+pub(crate) fn check(ret: c_int) -> Result<c_int, FFmpegError> {
+    if ret < 0 {
+        Err(FFmpegError::from_code(ret))
+    } else {
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posix_round_trip() {
+        assert_eq!(averror_to_posix(posix_to_averror(EINVAL)), Some(EINVAL));
+        for &errno in POSIX_ERRNOS {
+            assert_eq!(averror_to_posix(posix_to_averror(errno)), Some(errno));
+        }
+    }
+
+    #[test]
+    fn not_posix() {
+        assert_eq!(averror_to_posix(AVERROR_EOF), None);
+        assert_eq!(averror_to_posix(AVERROR_INVALIDDATA), None);
+        assert_eq!(averror_to_posix(0), None);
+        assert_eq!(averror_to_posix(EINVAL), None);
+        assert_eq!(averror_to_posix(c_int::MIN), None);
+    }
+
+    #[test]
+    fn error_strings() {
+        assert_eq!(strerror(AVERROR_EOF), "End of file");
+        assert_eq!(err2str(AVERROR_EOF), "End of file");
+        assert_eq!(FFmpegError::Eof.to_string(), "End of file");
+        assert_eq!(
+            strerror(AVERROR_BUG),
+            "Internal bug, should not have happened"
+        );
+    }
+}
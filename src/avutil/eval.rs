@@ -0,0 +1,169 @@
+use std::ffi::CString;
+use std::ptr;
+
+use libc::{c_char, c_double, c_void, EINVAL};
+use {av_expr_eval, av_expr_free, av_expr_parse, av_expr_parse_and_eval, AVExpr};
+
+use super::error::check;
+use super::{FFmpegError, AVERROR};
+
+pub type ExprFunc1 = unsafe extern "C" fn(*mut c_void, c_double) -> c_double;
+pub type ExprFunc2 = unsafe extern "C" fn(*mut c_void, c_double, c_double) -> c_double;
+
+// Owns the strings behind a NULL terminated `const char *const *` array.
+struct NameList {
+    _names: Vec<CString>,
+    ptrs: Vec<*const c_char>,
+}
+
+impl NameList {
+    fn new<'a, I: IntoIterator<Item = &'a str>>(names: I) -> Result<Self, FFmpegError> {
+        let names = names
+            .into_iter()
+            .map(CString::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut ptrs: Vec<_> = names.iter().map(|name| name.as_ptr()).collect();
+        ptrs.push(ptr::null());
+
+        Ok(NameList {
+            _names: names,
+            ptrs,
+        })
+    }
+
+    fn as_ptr(&self) -> *const *const c_char {
+        self.ptrs.as_ptr()
+    }
+}
+
+/// Parsed `libavutil/eval.h` expression.
+pub struct Expr(*mut AVExpr, usize);
+
+// SAFETY: AVExpr has no thread affinity. It is not Sync since av_expr_eval
+// updates the expression's internal variables; see the safety_notes module.
+unsafe impl Send for Expr {}
+
+impl Expr {
+    /// Parses `expr`, resolving `const_names` to the values later passed to
+    /// `eval` (in the same order) and the function names to the given
+    /// callbacks, which receive the `opaque` pointer passed to `eval`.
+    ///
+    /// `av_expr_parse` takes the names and the callbacks as two separate
+    /// arrays and crashes on a name without a callback, hence the pairs.
+    pub fn parse(
+        expr: &str,
+        const_names: &[&str],
+        func1: &[(&str, ExprFunc1)],
+        func2: &[(&str, ExprFunc2)],
+    ) -> Result<Self, FFmpegError> {
+        let expr = CString::new(expr)?;
+        let const_list = NameList::new(const_names.iter().cloned())?;
+        let func1_list = NameList::new(func1.iter().map(|f| f.0))?;
+        let func2_list = NameList::new(func2.iter().map(|f| f.0))?;
+        let funcs1: Vec<Option<ExprFunc1>> = func1.iter().map(|f| Some(f.1)).collect();
+        let funcs2: Vec<Option<ExprFunc2>> = func2.iter().map(|f| Some(f.1)).collect();
+
+        let mut ctx = ptr::null_mut();
+        unsafe {
+            check(av_expr_parse(
+                &mut ctx,
+                expr.as_ptr(),
+                const_list.as_ptr(),
+                func1_list.as_ptr(),
+                funcs1.as_ptr(),
+                func2_list.as_ptr(),
+                funcs2.as_ptr(),
+                0,
+                ptr::null_mut(),
+            ))?;
+        }
+
+        Ok(Expr(ctx, const_names.len()))
+    }
+
+    /// Evaluates the expression; `const_values` must hold a value for each
+    /// of the constant names given to `parse`, `opaque` is only handed to the
+    /// function callbacks.
+    pub unsafe fn eval(&self, const_values: &[f64], opaque: *mut c_void) -> f64 {
+        assert!(
+            const_values.len() >= self.1,
+            "expected {} constant values",
+            self.1
+        );
+
+        av_expr_eval(self.0, const_values.as_ptr(), opaque)
+    }
+}
+
+impl Drop for Expr {
+    fn drop(&mut self) {
+        unsafe {
+            av_expr_free(self.0);
+        }
+    }
+}
+
+/// Parses and evaluates `expr` in one go, without custom functions.
+pub fn eval_once(
+    expr: &str,
+    const_names: &[&str],
+    const_values: &[f64],
+) -> Result<f64, FFmpegError> {
+    if const_names.len() != const_values.len() {
+        return Err(FFmpegError::Other(AVERROR(EINVAL)));
+    }
+
+    let expr = CString::new(expr)?;
+    let const_list = NameList::new(const_names.iter().cloned())?;
+
+    let mut res = 0.0;
+    unsafe {
+        check(av_expr_parse_and_eval(
+            &mut res,
+            expr.as_ptr(),
+            const_list.as_ptr(),
+            const_values.as_ptr(),
+            ptr::null(),
+            ptr::null(),
+            ptr::null(),
+            ptr::null(),
+            ptr::null_mut(),
+            0,
+            ptr::null_mut(),
+        ))?;
+    }
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe extern "C" fn double(_opaque: *mut c_void, x: c_double) -> c_double {
+        2.0 * x
+    }
+
+    #[test]
+    fn eval_constant_expression() {
+        assert_eq!(eval_once("2+2", &[], &[]).unwrap(), 4.0);
+        assert!((eval_once("sin(PI/2)", &[], &[]).unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(eval_once("w*h", &["w", "h"], &[16.0, 9.0]).unwrap(), 144.0);
+    }
+
+    #[test]
+    fn eval_with_constants_and_functions() {
+        let expr = Expr::parse("double(x) + y", &["x", "y"], &[("double", double)], &[]).unwrap();
+
+        unsafe {
+            assert_eq!(expr.eval(&[3.0, 1.0], ptr::null_mut()), 7.0);
+            assert_eq!(expr.eval(&[0.5, -1.0], ptr::null_mut()), 0.0);
+        }
+    }
+
+    #[test]
+    fn eval_errors() {
+        assert!(eval_once("2+", &[], &[]).is_err());
+        assert!(eval_once("x", &["x"], &[]).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use std::marker::PhantomData;
+use std::mem;
+
+use libc::{c_void, ENOMEM};
+use {
+    av_fifo_alloc2, av_fifo_can_read, av_fifo_can_write, av_fifo_freep2, av_fifo_read,
+    av_fifo_write, AVFifo,
+};
+
+use super::error::{check, FFmpegError, AVERROR};
+
+/// Fixed-capacity `AVFifo` of `T` elements, which are copied in and out
+/// bytewise.
+pub struct Fifo<T: Copy>(*mut AVFifo, PhantomData<T>);
+
+// SAFETY: AVFifo has no thread affinity and is only accessed through
+// &mut self (or read-only through &self); see the safety_notes module.
+unsafe impl<T: Copy + Send> Send for Fifo<T> {}
+unsafe impl<T: Copy + Sync> Sync for Fifo<T> {}
+
+impl<T: Copy> Fifo<T> {
+    pub fn new(nb_elems: usize) -> Result<Self, FFmpegError> {
+        let fifo = unsafe { av_fifo_alloc2(nb_elems, mem::size_of::<T>(), 0) };
+        if fifo.is_null() {
+            return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+        }
+
+        Ok(Fifo(fifo, PhantomData))
+    }
+
+    /// Writes all of `data`, failing without writing anything if there is
+    /// not enough space.
+    pub fn write(&mut self, data: &[T]) -> Result<(), FFmpegError> {
+        unsafe {
+            check(av_fifo_write(
+                self.0,
+                data.as_ptr() as *const c_void,
+                data.len(),
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Fills all of `data`, failing without reading anything if there are
+    /// not enough elements.
+    pub fn read(&mut self, data: &mut [T]) -> Result<(), FFmpegError> {
+        unsafe {
+            check(av_fifo_read(
+                self.0,
+                data.as_mut_ptr() as *mut c_void,
+                data.len(),
+            ))?;
+        }
+        Ok(())
+    }
+
+    pub fn can_read(&self) -> usize {
+        unsafe { av_fifo_can_read(self.0) }
+    }
+
+    pub fn can_write(&self) -> usize {
+        unsafe { av_fifo_can_write(self.0) }
+    }
+
+    pub fn as_ptr(&self) -> *const AVFifo {
+        self.0
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut AVFifo {
+        self.0
+    }
+}
+
+impl<T: Copy> Drop for Fifo<T> {
+    fn drop(&mut self) {
+        unsafe {
+            av_fifo_freep2(&mut self.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fifo_u32() {
+        let mut fifo = Fifo::<u32>::new(4).unwrap();
+        assert_eq!((fifo.can_read(), fifo.can_write()), (0, 4));
+
+        fifo.write(&[1, 2, 3]).unwrap();
+        assert_eq!((fifo.can_read(), fifo.can_write()), (3, 1));
+        assert!(fifo.write(&[4, 5]).is_err());
+        assert_eq!(fifo.can_read(), 3);
+
+        let mut out = [0u32; 2];
+        fifo.read(&mut out).unwrap();
+        assert_eq!(out, [1, 2]);
+
+        // wraps around the end of the buffer
+        fifo.write(&[4, 5, 6]).unwrap();
+        let mut out = [0u32; 4];
+        fifo.read(&mut out).unwrap();
+        assert_eq!(out, [3, 4, 5, 6]);
+
+        assert!(fifo.read(&mut out[..1]).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+use std::ffi::CString;
+use std::ops::Deref;
+use std::ptr;
+use std::slice;
+
+use libc::c_void;
+use {av_file_map, av_file_unmap};
+
+use super::error::check;
+use super::FFmpegError;
+
+/// Read-only view of a file mapped with `av_file_map`, which falls back to
+/// reading the file into memory where mmap is not available.
+pub struct MappedFile {
+    ptr: *mut u8,
+    size: usize,
+}
+
+// SAFETY: the mapping is private and only ever read; see the safety_notes
+// module.
+unsafe impl Send for MappedFile {}
+unsafe impl Sync for MappedFile {}
+
+impl MappedFile {
+    /// Errors are logged against `log_ctx`, which may be null.
+    pub unsafe fn open(path: &str, log_ctx: *mut c_void) -> Result<Self, FFmpegError> {
+        let path = CString::new(path)?;
+        let mut ptr = ptr::null_mut();
+        let mut size = 0;
+
+        check(av_file_map(path.as_ptr(), &mut ptr, &mut size, 0, log_ctx))?;
+        Ok(MappedFile { ptr, size })
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // empty files are not mapped at all
+        if self.ptr.is_null() {
+            return &[];
+        }
+
+        unsafe { slice::from_raw_parts(self.ptr, self.size) }
+    }
+}
+
+impl Drop for MappedFile {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe {
+                av_file_unmap(self.ptr, self.size);
+            }
+        }
+    }
+}
+
+/// Reads the whole file at `path` through `av_file_map`.
+pub fn read_file(path: &str) -> Result<Vec<u8>, FFmpegError> {
+    let file = unsafe { MappedFile::open(path, ptr::null_mut())? };
+    Ok(file.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    #[test]
+    fn map_file() {
+        let path = env::temp_dir().join(format!("ffmpeg-sys-file-map-{}", process::id()));
+        let contents: Vec<u8> = (0..10000).map(|i| i as u8).collect();
+        fs::write(&path, &contents).unwrap();
+
+        let data = read_file(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        assert_eq!(data.unwrap(), contents);
+    }
+
+    #[test]
+    fn missing_file() {
+        assert!(read_file("/nonexistent/ffmpeg-sys-file-map").is_err());
+    }
+}
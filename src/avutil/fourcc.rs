@@ -0,0 +1,80 @@
+use std::fmt;
+
+/// FourCC code, with the first character in the least significant byte as
+/// produced by `MKTAG`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FourCC(pub u32);
+
+impl From<u32> for FourCC {
+    fn from(tag: u32) -> Self {
+        FourCC(tag)
+    }
+}
+
+impl From<[u8; 4]> for FourCC {
+    fn from(chars: [u8; 4]) -> Self {
+        FourCC(u32::from_le_bytes(chars))
+    }
+}
+
+impl From<FourCC> for u32 {
+    fn from(fourcc: FourCC) -> Self {
+        fourcc.0
+    }
+}
+
+impl From<FourCC> for [u8; 4] {
+    fn from(fourcc: FourCC) -> Self {
+        fourcc.0.to_le_bytes()
+    }
+}
+
+/// Same output as `av_fourcc_make_string`: alphanumeric characters, `.`,
+/// space, `-` and `_` as is, other bytes as `[<decimal value>]`.
+impl fmt::Display for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &c in &self.0.to_le_bytes() {
+            if c.is_ascii_alphanumeric() || b". -_".contains(&c) {
+                write!(f, "{}", c as char)?;
+            } else {
+                write!(f, "[{}]", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `av_fourcc_make_string` for all FFmpeg versions, it only exists since
+/// FFmpeg 4.1.
+pub fn av_fourcc_to_str(tag: u32) -> String {
+    FourCC(tag).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes() {
+        let avc1 = FourCC::from(*b"avc1");
+        assert_eq!(avc1.0, 0x3163_7661);
+        assert_eq!(avc1.to_string(), "avc1");
+        assert_eq!(<[u8; 4]>::from(avc1), *b"avc1");
+
+        let vp90 = FourCC::from(*b"VP90");
+        assert_eq!(u32::from(vp90), u32::from_le_bytes(*b"VP90"));
+        assert_eq!(vp90.to_string(), "VP90");
+
+        let mp4a = FourCC::from(*b"mp4a");
+        assert_eq!(mp4a.0, 0x6134_706d);
+        assert_eq!(av_fourcc_to_str(mp4a.0), "mp4a");
+    }
+
+    #[test]
+    fn non_printable() {
+        assert_eq!(
+            av_fourcc_to_str(u32::from_le_bytes([b'Y', 1, 0, b' '])),
+            "Y[1][0] "
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use libc::{c_int, ENOMEM};
+use {
+    av_hash_alloc, av_hash_final, av_hash_freep, av_hash_get_name, av_hash_get_size, av_hash_init,
+    av_hash_names, av_hash_update, AVHashContext,
+};
+
+use super::error::check;
+use super::{FFmpegError, AVERROR};
+
+/// Generic `AVHashContext`, for any of the algorithms listed by
+/// `av_hash_names` ("MD5", "SHA256", "murmur3", "adler32", ...).
+pub struct Hash(*mut AVHashContext);
+
+// SAFETY: AVHashContext has no thread affinity and is only mutated through
+// &mut self; see the safety_notes module.
+unsafe impl Send for Hash {}
+
+impl Hash {
+    pub fn new(name: &str) -> Result<Self, FFmpegError> {
+        let name = CString::new(name)?;
+        let mut ctx = ptr::null_mut();
+
+        unsafe {
+            check(av_hash_alloc(&mut ctx, name.as_ptr()))?;
+            if ctx.is_null() {
+                return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+            }
+            av_hash_init(ctx);
+        }
+
+        Ok(Hash(ctx))
+    }
+
+    /// Resets the context, discarding any data hashed so far.
+    pub fn init(&mut self) {
+        unsafe {
+            av_hash_init(self.0);
+        }
+    }
+
+    /// Canonical name of the algorithm, which may differ in case from the
+    /// one passed to `new`.
+    pub fn name(&self) -> &'static str {
+        unsafe {
+            CStr::from_ptr(av_hash_get_name(self.0))
+                .to_str()
+                .unwrap_or("")
+        }
+    }
+
+    pub fn digest_size(&self) -> usize {
+        unsafe { av_hash_get_size(self.0) as usize }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        unsafe {
+            av_hash_update(self.0, data.as_ptr(), data.len() as _);
+        }
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        unsafe {
+            let mut digest = vec![0u8; av_hash_get_size(self.0) as usize];
+            av_hash_final(self.0, digest.as_mut_ptr());
+            digest
+        }
+    }
+
+    /// Like `finalize`, but resets the context so that it can be reused.
+    pub fn finalize_reset(&mut self) -> Vec<u8> {
+        unsafe {
+            let mut digest = vec![0u8; self.digest_size()];
+            av_hash_final(self.0, digest.as_mut_ptr());
+            av_hash_init(self.0);
+            digest
+        }
+    }
+}
+
+impl Drop for Hash {
+    fn drop(&mut self) {
+        unsafe {
+            av_hash_freep(&mut self.0);
+        }
+    }
+}
+
+/// Iterator over the algorithm names accepted by `Hash::new`.
+pub struct HashNames(c_int);
+
+impl Iterator for HashNames {
+    type Item = &'static str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            let name = av_hash_names(self.0);
+            if name.is_null() {
+                return None;
+            }
+
+            self.0 += 1;
+            CStr::from_ptr(name).to_str().ok()
+        }
+    }
+}
+
+pub fn hash_names() -> HashNames {
+    HashNames(0)
+}
+
+fn hash_oneshot(name: &str, data: &[u8], out: &mut [u8]) {
+    let mut hash = Hash::new(name).expect("hash algorithm not available");
+    hash.update(data);
+    out.copy_from_slice(&hash.finalize());
+}
+
+pub fn md5(data: &[u8]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    hash_oneshot("MD5", data, &mut out);
+    out
+}
+
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    hash_oneshot("SHA256", data, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::hex;
+
+    #[test]
+    fn md5_empty() {
+        assert_eq!(hex(&md5(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+    }
+
+    #[test]
+    fn sha256_abc() {
+        assert_eq!(
+            hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        let mut hash = Hash::new("SHA256").unwrap();
+        hash.update(b"a");
+        hash.update(b"bc");
+        assert_eq!(hash.finalize(), sha256(b"abc").to_vec());
+    }
+
+    #[test]
+    fn unknown_algorithm() {
+        assert!(Hash::new("no-such-hash").is_err());
+    }
+
+    // digests of "abc"
+    const VECTORS: &[(&str, &str)] = &[
+        ("MD5", "900150983cd24fb0d6963f7d28e17f72"),
+        ("murmur3", "24f8c0b6239d906515c11aef9def41d2"),
+        ("RIPEMD128", "c14a12199c66e4ba84636b0f69144c77"),
+        ("RIPEMD160", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+        (
+            "RIPEMD256",
+            "afbd6e228b9d8cbbcef5ca2d03e6dba10ac0bc7dcbe4680e1e42d2e975459b65",
+        ),
+        (
+            "RIPEMD320",
+            "de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1b8d116713e74f82fa942d64cdbc4682d",
+        ),
+        ("SHA160", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+        (
+            "SHA224",
+            "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+        ),
+        (
+            "SHA256",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        (
+            "SHA512/224",
+            "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa",
+        ),
+        (
+            "SHA512/256",
+            "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23",
+        ),
+        (
+            "SHA384",
+            "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7",
+        ),
+        (
+            "SHA512",
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+        ),
+        ("CRC32", "352441c2"),
+        ("adler32", "024d0127"),
+    ];
+
+    #[test]
+    fn all_algorithms() {
+        for name in hash_names() {
+            let expected = VECTORS
+                .iter()
+                .find(|v| v.0 == name)
+                .unwrap_or_else(|| panic!("no test vector for {}", name))
+                .1;
+
+            let mut hash = Hash::new(name).unwrap();
+            assert_eq!(hash.name(), name);
+            assert_eq!(hash.digest_size() * 2, expected.len(), "{}", name);
+            hash.update(b"abc");
+            assert_eq!(hex(&hash.finalize()), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn canonical_name() {
+        assert_eq!(Hash::new("sha256").unwrap().name(), "SHA256");
+    }
+
+    #[test]
+    fn finalize_reset() {
+        let mut hash = Hash::new("MD5").unwrap();
+        hash.update(b"abc");
+        let first = hash.finalize_reset();
+        hash.update(b"abc");
+        assert_eq!(hash.finalize_reset(), first);
+        assert_eq!(hex(&hash.finalize()), "d41d8cd98f00b204e9800998ecf8427e");
+    }
+}
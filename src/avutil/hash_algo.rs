@@ -0,0 +1,213 @@
+#[cfg(feature = "digest")]
+use digest;
+use libc::c_void;
+use {
+    av_free, av_md5_alloc, av_md5_final, av_md5_init, av_md5_update, av_ripemd_alloc,
+    av_ripemd_final, av_ripemd_init, av_ripemd_update, av_sha512_alloc, av_sha512_final,
+    av_sha512_init, av_sha512_update, av_sha_alloc, av_sha_final, av_sha_init, av_sha_update,
+    AVMD5, AVRIPEMD, AVSHA, AVSHA512,
+};
+
+/// Streaming interface shared by the fixed algorithm hashers below.
+pub trait HashAlgorithm {
+    fn update(&mut self, data: &[u8]);
+
+    /// Writes the digest into the first `digest_size()` bytes of `dst` and
+    /// resets the hasher, so that it can be reused.
+    fn finalize(&mut self, dst: &mut [u8]);
+
+    fn digest_size() -> usize;
+}
+
+macro_rules! hash_algorithm {
+    (
+        $name:ident, $ctx:ty, $size:expr, $digest_size:ident,
+        alloc: $alloc:ident, init: $init:expr, update: $update:ident, final: $final:ident
+    ) => {
+        pub struct $name(*mut $ctx);
+
+        // SAFETY: the hash contexts have no thread affinity and are only
+        // mutated through &mut self; see the safety_notes module.
+        unsafe impl Send for $name {}
+
+        impl $name {
+            pub fn new() -> Self {
+                let ctx = unsafe { $alloc() };
+                assert!(!ctx.is_null(), concat!(stringify!($alloc), " failed"));
+
+                let mut hasher = $name(ctx);
+                hasher.init();
+                hasher
+            }
+
+            fn init(&mut self) {
+                unsafe {
+                    $init(self.0);
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name::new()
+            }
+        }
+
+        impl HashAlgorithm for $name {
+            fn update(&mut self, data: &[u8]) {
+                unsafe {
+                    $update(self.0, data.as_ptr(), data.len() as _);
+                }
+            }
+
+            fn finalize(&mut self, dst: &mut [u8]) {
+                assert!(dst.len() >= $size, "digest buffer too small");
+
+                unsafe {
+                    $final(self.0, dst.as_mut_ptr());
+                }
+                self.init();
+            }
+
+            fn digest_size() -> usize {
+                $size
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                unsafe {
+                    av_free(self.0 as *mut c_void);
+                }
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl digest::HashMarker for $name {}
+
+        #[cfg(feature = "digest")]
+        impl digest::OutputSizeUser for $name {
+            type OutputSize = digest::consts::$digest_size;
+        }
+
+        #[cfg(feature = "digest")]
+        impl digest::Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                HashAlgorithm::update(self, data);
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl digest::FixedOutput for $name {
+            fn finalize_into(mut self, out: &mut digest::Output<Self>) {
+                HashAlgorithm::finalize(&mut self, out);
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl digest::FixedOutputReset for $name {
+            fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+                HashAlgorithm::finalize(self, out);
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl digest::Reset for $name {
+            fn reset(&mut self) {
+                self.init();
+            }
+        }
+    };
+}
+
+hash_algorithm!(Md5, AVMD5, 16, U16,
+    alloc: av_md5_alloc, init: av_md5_init, update: av_md5_update, final: av_md5_final);
+hash_algorithm!(Sha1, AVSHA, 20, U20,
+    alloc: av_sha_alloc, init: |ctx| av_sha_init(ctx, 160), update: av_sha_update,
+    final: av_sha_final);
+hash_algorithm!(Sha256, AVSHA, 32, U32,
+    alloc: av_sha_alloc, init: |ctx| av_sha_init(ctx, 256), update: av_sha_update,
+    final: av_sha_final);
+hash_algorithm!(Sha512, AVSHA512, 64, U64,
+    alloc: av_sha512_alloc, init: |ctx| av_sha512_init(ctx, 512), update: av_sha512_update,
+    final: av_sha512_final);
+hash_algorithm!(RipeMd160, AVRIPEMD, 20, U20,
+    alloc: av_ripemd_alloc, init: |ctx| av_ripemd_init(ctx, 160), update: av_ripemd_update,
+    final: av_ripemd_final);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::hex;
+
+    fn hex_digest<H: HashAlgorithm>(hasher: &mut H, data: &[u8]) -> String {
+        let mut out = vec![0; H::digest_size()];
+        hasher.update(data);
+        hasher.finalize(&mut out);
+        hex(&out)
+    }
+
+    #[test]
+    fn md5_empty() {
+        assert_eq!(
+            hex_digest(&mut Md5::new(), b""),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+    }
+
+    #[test]
+    fn sha_abc() {
+        assert_eq!(
+            hex_digest(&mut Sha1::new(), b"abc"),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            hex_digest(&mut Sha256::new(), b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex_digest(&mut Sha512::new(), b"abc"),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+    }
+
+    #[test]
+    fn ripemd160_abc() {
+        assert_eq!(
+            hex_digest(&mut RipeMd160::new(), b"abc"),
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"
+        );
+    }
+
+    #[test]
+    fn finalize_resets() {
+        let mut hasher = Sha256::new();
+        hasher.update(b"garbage");
+        let mut out = [0; 32];
+        hasher.finalize(&mut out);
+
+        assert_eq!(
+            hex_digest(&mut hasher, b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn digest_traits() {
+        use digest::Digest;
+
+        assert_eq!(
+            hex(&Sha1::digest(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+
+        let mut hasher = Md5::new();
+        Digest::update(&mut hasher, b"");
+        assert_eq!(
+            hex(&hasher.finalize_reset()),
+            "d41d8cd98f00b204e9800998ecf8427e"
+        );
+    }
+}
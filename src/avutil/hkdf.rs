@@ -0,0 +1,77 @@
+use libc::EINVAL;
+use AVHMACType;
+
+use super::{hmac_sha256, FFmpegError, Hmac, AVERROR};
+
+/// HKDF-Extract (RFC 5869) with SHA-256, returning the pseudorandom key.
+///
+/// An empty `salt` is the same as 32 zero bytes, as HMAC pads keys with
+/// zeros anyway.
+pub fn hkdf_extract(salt: &[u8], ikm: &[u8]) -> [u8; 32] {
+    hmac_sha256(salt, ikm)
+}
+
+/// HKDF-Expand (RFC 5869) with SHA-256, failing with `AVERROR(EINVAL)` if
+/// `len` is above the 255 * 32 bytes limit.
+pub fn hkdf_expand(prk: &[u8; 32], info: &[u8], len: usize) -> Result<Vec<u8>, FFmpegError> {
+    if len > 255 * 32 {
+        return Err(FFmpegError::Other(AVERROR(EINVAL)));
+    }
+
+    let mut hmac = Hmac::new(AVHMACType::AV_HMAC_SHA256)?;
+    let mut okm = Vec::with_capacity(len);
+    let mut block = [0u8; 32];
+
+    for counter in 1..=255u8 {
+        if okm.len() >= len {
+            break;
+        }
+
+        hmac.init(prk);
+        if counter > 1 {
+            hmac.update(&block);
+        }
+        hmac.update(info);
+        hmac.update(&[counter]);
+        hmac.finalize(&mut block)?;
+
+        let take = (len - okm.len()).min(block.len());
+        okm.extend_from_slice(&block[..take]);
+    }
+
+    Ok(okm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::hex;
+
+    // RFC 5869, test case 1
+    #[test]
+    fn rfc5869() {
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+
+        let prk = hkdf_extract(&salt, &[0x0b; 22]);
+        assert_eq!(
+            hex(&prk),
+            "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5"
+        );
+
+        let okm = hkdf_expand(&prk, &info, 42).unwrap();
+        assert_eq!(
+            hex(&okm),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c\
+             5db02d56ecc4c5bf34007208d5b887185865"
+        );
+    }
+
+    #[test]
+    fn expand_limits() {
+        let prk = [0u8; 32];
+        assert!(hkdf_expand(&prk, b"", 0).unwrap().is_empty());
+        assert_eq!(hkdf_expand(&prk, b"", 255 * 32).unwrap().len(), 255 * 32);
+        assert!(hkdf_expand(&prk, b"", 255 * 32 + 1).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use libc::ENOMEM;
+use {
+    av_hmac_alloc, av_hmac_final, av_hmac_free, av_hmac_init, av_hmac_update, AVHMACType, AVHMAC,
+};
+
+use super::error::check;
+use super::{FFmpegError, AVERROR};
+
+/// `AVHMAC` context; `init` has to be called with the key before each
+/// message.
+pub struct Hmac(*mut AVHMAC);
+
+// SAFETY: AVHMAC has no thread affinity and is only mutated through
+// &mut self; see the safety_notes module.
+unsafe impl Send for Hmac {}
+
+impl Hmac {
+    pub fn new(type_: AVHMACType) -> Result<Self, FFmpegError> {
+        let ctx = unsafe { av_hmac_alloc(type_) };
+        if ctx.is_null() {
+            // also returned for types libavutil does not know about
+            return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+        }
+
+        Ok(Hmac(ctx))
+    }
+
+    pub fn init(&mut self, key: &[u8]) {
+        unsafe {
+            av_hmac_init(self.0, key.as_ptr(), key.len() as _);
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        unsafe {
+            av_hmac_update(self.0, data.as_ptr(), data.len() as _);
+        }
+    }
+
+    /// Writes the MAC into `out` and returns its length, failing with
+    /// `AVERROR(EINVAL)` if `out` is too small.
+    pub fn finalize(&mut self, out: &mut [u8]) -> Result<usize, FFmpegError> {
+        let len = unsafe { check(av_hmac_final(self.0, out.as_mut_ptr(), out.len() as _))? };
+        Ok(len as usize)
+    }
+}
+
+impl Drop for Hmac {
+    fn drop(&mut self) {
+        unsafe {
+            av_hmac_free(self.0);
+        }
+    }
+}
+
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut hmac = Hmac::new(AVHMACType::AV_HMAC_SHA256).expect("av_hmac_alloc failed");
+    let mut out = [0u8; 32];
+
+    hmac.init(key);
+    hmac.update(data);
+    hmac.finalize(&mut out)
+        .expect("32 bytes fit an HMAC-SHA256");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::hex;
+
+    // RFC 4231, test cases 1 and 2
+    #[test]
+    fn rfc4231() {
+        assert_eq!(
+            hex(&hmac_sha256(&[0x0b; 20], b"Hi There")),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn streaming_and_reuse() {
+        let mut hmac = Hmac::new(AVHMACType::AV_HMAC_SHA256).unwrap();
+        let mut out = [0u8; 32];
+
+        for _ in 0..2 {
+            hmac.init(b"Jefe");
+            hmac.update(b"what do ya want ");
+            hmac.update(b"for nothing?");
+            assert_eq!(hmac.finalize(&mut out).unwrap(), 32);
+            assert_eq!(out, hmac_sha256(b"Jefe", b"what do ya want for nothing?"));
+        }
+    }
+
+    #[test]
+    fn output_too_small() {
+        let mut hmac = Hmac::new(AVHMACType::AV_HMAC_SHA256).unwrap();
+        hmac.init(b"key");
+        assert!(hmac.finalize(&mut [0u8; 16]).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use libc::{c_int, ENOMEM};
+use {
+    av_hwdevice_ctx_create, av_hwdevice_get_type_name, av_hwdevice_iterate_types,
+    av_hwframe_ctx_alloc, AVBufferRef, AVDictionary, AVHWDeviceType,
+};
+
+use super::error::{check, FFmpegError, AVERROR};
+
+/// Iterator over the device types supported by this build of libavutil.
+pub struct HwDeviceTypes(AVHWDeviceType);
+
+impl Iterator for HwDeviceTypes {
+    type Item = AVHWDeviceType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0 = unsafe { av_hwdevice_iterate_types(self.0) };
+        if self.0 == AVHWDeviceType::AV_HWDEVICE_TYPE_NONE {
+            None
+        } else {
+            Some(self.0)
+        }
+    }
+}
+
+pub fn hw_device_types() -> HwDeviceTypes {
+    HwDeviceTypes(AVHWDeviceType::AV_HWDEVICE_TYPE_NONE)
+}
+
+/// Opens a device of the given type and returns a new reference to its
+/// `AVHWDeviceContext`, to be released with `av_buffer_unref`.
+///
+/// The meaning of `device` depends on the type, e.g. a DRM node for VAAPI or
+/// an index for CUDA; `None` picks the default device.
+pub unsafe fn create_hw_device_ctx(
+    type_: AVHWDeviceType,
+    device: Option<&str>,
+    opts: Option<*mut AVDictionary>,
+    flags: c_int,
+) -> Result<*mut AVBufferRef, FFmpegError> {
+    let device = match device {
+        Some(device) => Some(CString::new(device)?),
+        None => None,
+    };
+    let mut device_ref = ptr::null_mut();
+
+    check(av_hwdevice_ctx_create(
+        &mut device_ref,
+        type_,
+        device
+            .as_ref()
+            .map_or(ptr::null(), |device| device.as_ptr()),
+        opts.unwrap_or(ptr::null_mut()),
+        flags,
+    ))?;
+
+    Ok(device_ref)
+}
+
+/// Name of a device type as accepted by `av_hwdevice_find_type_by_name`,
+/// "unknown" for `AV_HWDEVICE_TYPE_NONE`.
+pub fn hw_device_type_name(type_: AVHWDeviceType) -> &'static str {
+    unsafe {
+        let name = av_hwdevice_get_type_name(type_);
+        if name.is_null() {
+            return "unknown";
+        }
+
+        CStr::from_ptr(name).to_str().unwrap_or("unknown")
+    }
+}
+
+/// Allocates an `AVHWFramesContext` on `device_ctx`; the caller fills it in
+/// and initializes it with `av_hwframe_ctx_init`.
+pub unsafe fn alloc_hw_frames_ctx(
+    device_ctx: *mut AVBufferRef,
+) -> Result<*mut AVBufferRef, FFmpegError> {
+    let frames_ref = av_hwframe_ctx_alloc(device_ctx);
+    if frames_ref.is_null() {
+        return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+    }
+
+    Ok(frames_ref)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use av_hwdevice_find_type_by_name;
+
+    #[test]
+    fn device_types() {
+        // the list depends on the build, but every type has a name that maps
+        // back to it
+        for type_ in hw_device_types() {
+            let name = hw_device_type_name(type_);
+            assert_ne!(name, "unknown");
+
+            let name = CString::new(name).unwrap();
+            assert_eq!(
+                unsafe { av_hwdevice_find_type_by_name(name.as_ptr()) },
+                type_
+            );
+        }
+
+        assert_eq!(
+            hw_device_type_name(AVHWDeviceType::AV_HWDEVICE_TYPE_NONE),
+            "unknown"
+        );
+    }
+}
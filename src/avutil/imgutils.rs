@@ -0,0 +1,102 @@
+use libc::c_int;
+use {av_image_fill_arrays, av_image_fill_linesizes, av_image_get_buffer_size, AVPixelFormat};
+
+use super::error::check;
+use super::FFmpegError;
+
+/// Size in bytes of a buffer holding an image with the given parameters.
+pub fn image_buffer_size(
+    pix_fmt: AVPixelFormat,
+    width: c_int,
+    height: c_int,
+    align: c_int,
+) -> Result<usize, FFmpegError> {
+    let size = unsafe { check(av_image_get_buffer_size(pix_fmt, width, height, align))? };
+    Ok(size as usize)
+}
+
+/// Sets up `data` and `linesize` to point into `buf`, which must be at least
+/// `image_buffer_size` bytes (or null, to only compute the pointers'
+/// offsets).
+pub unsafe fn image_fill_arrays(
+    data: &mut [*mut u8; 4],
+    linesize: &mut [c_int; 4],
+    buf: *const u8,
+    pix_fmt: AVPixelFormat,
+    width: c_int,
+    height: c_int,
+    align: c_int,
+) -> Result<(), FFmpegError> {
+    check(av_image_fill_arrays(
+        data.as_mut_ptr(),
+        linesize.as_mut_ptr(),
+        buf,
+        pix_fmt,
+        width,
+        height,
+        align,
+    ))?;
+    Ok(())
+}
+
+pub fn image_fill_linesizes(
+    linesize: &mut [c_int; 4],
+    pix_fmt: AVPixelFormat,
+    width: c_int,
+) -> Result<(), FFmpegError> {
+    unsafe {
+        check(av_image_fill_linesizes(
+            linesize.as_mut_ptr(),
+            pix_fmt,
+            width,
+        ))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    use AVPixelFormat::AV_PIX_FMT_YUV420P;
+
+    #[test]
+    fn buffer_size() {
+        // only the line sizes are aligned, not the height
+        assert_eq!(
+            image_buffer_size(AV_PIX_FMT_YUV420P, 1920, 1080, 32).unwrap(),
+            3110400
+        );
+        // the same frame padded to whole 16x16 macroblocks
+        assert_eq!(
+            image_buffer_size(AV_PIX_FMT_YUV420P, 1920, 1088, 32).unwrap(),
+            3133440
+        );
+        assert!(image_buffer_size(AV_PIX_FMT_YUV420P, -1, 1080, 32).is_err());
+    }
+
+    #[test]
+    fn plane_layout() {
+        let mut linesize = [0; 4];
+        image_fill_linesizes(&mut linesize, AV_PIX_FMT_YUV420P, 1918).unwrap();
+        assert_eq!(linesize, [1918, 959, 959, 0]);
+
+        let mut data = [ptr::null_mut(); 4];
+        unsafe {
+            image_fill_arrays(
+                &mut data,
+                &mut linesize,
+                ptr::null(),
+                AV_PIX_FMT_YUV420P,
+                1920,
+                1080,
+                32,
+            )
+            .unwrap();
+        }
+        assert_eq!(linesize, [1920, 960, 960, 0]);
+        assert_eq!(data[1] as usize - data[0] as usize, 1920 * 1080);
+        assert_eq!(data[2] as usize - data[1] as usize, 960 * 540);
+    }
+}
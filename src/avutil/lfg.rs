@@ -0,0 +1,169 @@
+use std::mem;
+
+#[cfg(feature = "rand-core")]
+use rand_core::{impls, Error, RngCore};
+use {av_bmg_get, av_lfg_init, AVLFG};
+
+/// Lagged Fibonacci generator, stack allocated.
+pub struct Lfg(AVLFG);
+
+impl Lfg {
+    pub fn new(seed: u32) -> Self {
+        unsafe {
+            let mut lfg = Lfg(mem::zeroed());
+            av_lfg_init(&mut lfg.0, seed);
+            lfg
+        }
+    }
+
+    /// Rust version of the `av_lfg_get` inline function.
+    pub fn next_u32(&mut self) -> u32 {
+        let c = &mut self.0;
+        let a = c.state[(c.index.wrapping_sub(24) & 63) as usize]
+            .wrapping_add(c.state[(c.index.wrapping_sub(55) & 63) as usize]);
+        c.state[(c.index & 63) as usize] = a;
+        c.index = c.index.wrapping_add(1);
+        a
+    }
+
+    /// Normally distributed value (mean 0, variance 1) obtained through
+    /// `av_bmg_get`; the second value of each generated pair is discarded,
+    /// use `Bmg` to keep both.
+    pub fn next_gaussian(&mut self) -> f64 {
+        let mut out = [0f64; 2];
+        unsafe {
+            av_bmg_get(&mut self.0, out.as_mut_ptr());
+        }
+        out[0]
+    }
+}
+
+#[cfg(feature = "rand-core")]
+impl RngCore for Lfg {
+    fn next_u32(&mut self) -> u32 {
+        Lfg::next_u32(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Box-Muller gaussian generator on top of `Lfg`, using both values produced
+/// by each `av_bmg_get` call.
+pub struct Bmg {
+    lfg: Lfg,
+    spare: Option<f64>,
+}
+
+impl Bmg {
+    pub fn new(seed: u32) -> Self {
+        Bmg {
+            lfg: Lfg::new(seed),
+            spare: None,
+        }
+    }
+
+    pub fn next_f64(&mut self) -> f64 {
+        if let Some(value) = self.spare.take() {
+            return value;
+        }
+
+        let mut out = [0f64; 2];
+        unsafe {
+            av_bmg_get(&mut self.lfg.0, out.as_mut_ptr());
+        }
+        self.spare = Some(out[1]);
+        out[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chi_squared(counts: &[u32]) -> f64 {
+        let total: u32 = counts.iter().sum();
+        let expected = f64::from(total) / counts.len() as f64;
+        counts
+            .iter()
+            .map(|&c| (f64::from(c) - expected).powi(2) / expected)
+            .sum()
+    }
+
+    #[test]
+    fn lfg_known_values() {
+        // av_lfg_init seeds the state through MD5, so the output is fixed
+        // for a given seed
+        let mut lfg = Lfg::new(0xdead_beef);
+        let values: Vec<u32> = (0..5).map(|_| lfg.next_u32()).collect();
+        assert_eq!(
+            values,
+            [
+                0xa896_88cc,
+                0xcf4e_b0f3,
+                0xdf46_1dee,
+                0xa734_e989,
+                0x2459_2a9f
+            ]
+        );
+
+        let mut lfg = Lfg::new(0);
+        assert_eq!(lfg.next_u32(), 0xe6c5_ddb1);
+    }
+
+    #[test]
+    fn lfg_chi_squared() {
+        let mut lfg = Lfg::new(0xdead_beef);
+        let mut counts = [0u32; 16];
+        for _ in 0..160_000 {
+            counts[(lfg.next_u32() >> 28) as usize] += 1;
+        }
+
+        // 15 degrees of freedom, p = 0.001
+        assert!(chi_squared(&counts) < 37.7);
+    }
+
+    #[test]
+    fn bmg_chi_squared() {
+        // octiles of the standard normal distribution
+        let edges = [
+            -1.150_349_4,
+            -0.674_489_8,
+            -0.318_639_4,
+            0.0,
+            0.318_639_4,
+            0.674_489_8,
+            1.150_349_4,
+        ];
+
+        let mut bmg = Bmg::new(0xdead_beef);
+        let mut counts = [0u32; 8];
+        let mut sum = 0.0;
+        let mut sum_sq = 0.0;
+        let n = 80_000;
+        for _ in 0..n {
+            let value = bmg.next_f64();
+            counts[edges.iter().filter(|&&e| value >= e).count()] += 1;
+            sum += value;
+            sum_sq += value * value;
+        }
+
+        // 7 degrees of freedom, p = 0.001
+        assert!(chi_squared(&counts) < 24.3);
+
+        let mean = sum / f64::from(n);
+        let variance = sum_sq / f64::from(n) - mean * mean;
+        assert!(mean.abs() < 0.02);
+        assert!((variance - 1.0).abs() < 0.03);
+    }
+}
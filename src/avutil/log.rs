@@ -0,0 +1,46 @@
+#[cfg(feature = "ffmpeg_4_3")]
+use std::ffi::CString;
+
+use libc::c_int;
+#[cfg(feature = "ffmpeg_4_3")]
+use libc::c_void;
+use {av_log_get_flags, av_log_set_flags};
+
+#[cfg(feature = "ffmpeg_4_3")]
+use av_log_once;
+
+#[cfg(feature = "ffmpeg_4_3")]
+use super::FFmpegError;
+
+/// Sets the `AV_LOG_SKIP_REPEATED`/`AV_LOG_PRINT_LEVEL` flags of the default
+/// log callback.
+pub fn set_log_flags(flags: c_int) {
+    unsafe { av_log_set_flags(flags) }
+}
+
+pub fn get_log_flags() -> c_int {
+    unsafe { av_log_get_flags() }
+}
+
+/// Logs `msg` at `initial_level` the first time and at `subsequent_level`
+/// afterwards, with `state` (initially 0) tracking which one is due.
+#[cfg(feature = "ffmpeg_4_3")]
+pub unsafe fn log_once(
+    avcl: *mut c_void,
+    initial_level: c_int,
+    subsequent_level: c_int,
+    state: &mut c_int,
+    msg: &str,
+) -> Result<(), FFmpegError> {
+    let msg = CString::new(msg)?;
+
+    av_log_once(
+        avcl,
+        initial_level,
+        subsequent_level,
+        state,
+        b"%s\0".as_ptr() as *const _,
+        msg.as_ptr(),
+    );
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use std::ffi::CStr;
+
+#[cfg(all(target_arch = "x86_64", unix))]
+use __va_list_tag;
+use libc::{c_char, c_int, c_void};
+#[cfg(not(all(target_arch = "x86_64", unix)))]
+use va_list;
+use {
+    av_log_format_line2, av_log_get_level, av_log_set_callback, AV_LOG_DEBUG, AV_LOG_ERROR,
+    AV_LOG_INFO, AV_LOG_WARNING,
+};
+
+// The System V x86_64 va_list is an array type, which bindgen turns into
+// `[__va_list_tag; 1]`, while function arguments of that type decay to a
+// pointer; av_log_set_callback and av_log_format_line2 take the latter.
+#[cfg(all(target_arch = "x86_64", unix))]
+type VaList = *mut __va_list_tag;
+#[cfg(not(all(target_arch = "x86_64", unix)))]
+type VaList = va_list;
+
+/// Formats a message handed to a log callback the way the default callback
+/// does, `None` if it is filtered out by `av_log_set_level` or empty.
+unsafe fn format_line(
+    avcl: *mut c_void,
+    level: c_int,
+    fmt: *const c_char,
+    vl: VaList,
+) -> Option<String> {
+    if level > av_log_get_level() {
+        return None;
+    }
+
+    // every call is formatted on its own, so each one gets the
+    // "[class @ ptr] " prefix even if the previous one didn't end the line
+    let mut print_prefix = 1;
+    let mut buf = [0 as c_char; 1024];
+    if av_log_format_line2(
+        avcl,
+        level,
+        fmt,
+        vl,
+        buf.as_mut_ptr(),
+        buf.len() as c_int,
+        &mut print_prefix,
+    ) < 0
+    {
+        return None;
+    }
+
+    // longer messages are truncated, vl can't be consumed a second time
+    let line = CStr::from_ptr(buf.as_ptr()).to_string_lossy();
+    let line = line.trim_end();
+    if line.is_empty() {
+        None
+    } else {
+        Some(line.to_string())
+    }
+}
+
+#[cfg(feature = "tracing")]
+unsafe extern "C" fn tracing_callback(
+    avcl: *mut c_void,
+    level: c_int,
+    fmt: *const c_char,
+    vl: VaList,
+) {
+    let line = match format_line(avcl, level, fmt, vl) {
+        Some(line) => line,
+        None => return,
+    };
+
+    if level <= AV_LOG_ERROR {
+        ::tracing::error!(target: "ffmpeg", "{}", line);
+    } else if level <= AV_LOG_WARNING {
+        ::tracing::warn!(target: "ffmpeg", "{}", line);
+    } else if level <= AV_LOG_INFO {
+        ::tracing::info!(target: "ffmpeg", "{}", line);
+    } else if level <= AV_LOG_DEBUG {
+        ::tracing::debug!(target: "ffmpeg", "{}", line);
+    } else {
+        ::tracing::trace!(target: "ffmpeg", "{}", line);
+    }
+}
+
+/// Replaces the FFmpeg log callback with one emitting `tracing` events with
+/// the `ffmpeg` target. `AV_LOG_VERBOSE` maps to debug, everything below
+/// `AV_LOG_ERROR` to error.
+///
+/// Messages above the `av_log_set_level` level are still dropped before
+/// reaching `tracing`.
+#[cfg(feature = "tracing")]
+pub fn init_tracing_log() {
+    unsafe { av_log_set_callback(Some(tracing_callback)) }
+}
+
+#[cfg(feature = "log")]
+unsafe extern "C" fn log_callback(avcl: *mut c_void, level: c_int, fmt: *const c_char, vl: VaList) {
+    let line = match format_line(avcl, level, fmt, vl) {
+        Some(line) => line,
+        None => return,
+    };
+
+    let level = if level <= AV_LOG_ERROR {
+        ::log::Level::Error
+    } else if level <= AV_LOG_WARNING {
+        ::log::Level::Warn
+    } else if level <= AV_LOG_INFO {
+        ::log::Level::Info
+    } else if level <= AV_LOG_DEBUG {
+        ::log::Level::Debug
+    } else {
+        ::log::Level::Trace
+    };
+    ::log::log!(target: "ffmpeg", level, "{}", line);
+}
+
+/// Replaces the FFmpeg log callback with one forwarding messages to the
+/// `log` crate with the `ffmpeg` target, mapping levels like
+/// `init_tracing_log`.
+#[cfg(feature = "log")]
+pub fn init_log() {
+    unsafe { av_log_set_callback(Some(log_callback)) }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::ptr;
+    use std::sync::{Arc, Mutex};
+    use {av_log, av_log_default_callback};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn tracing_events() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_max_level(::tracing::Level::TRACE)
+            .with_ansi(false)
+            .without_time()
+            .finish();
+
+        // subscribers are per thread, and so are the events emitted by the
+        // callback for messages logged on this one
+        ::tracing::subscriber::with_default(subscriber, || unsafe {
+            init_tracing_log();
+            av_log(
+                ptr::null_mut(),
+                AV_LOG_WARNING,
+                b"disk %s\n\0".as_ptr() as *const c_char,
+                b"full\0".as_ptr() as *const c_char,
+            );
+            // above the default AV_LOG_INFO level
+            av_log(
+                ptr::null_mut(),
+                AV_LOG_DEBUG,
+                b"dropped\n\0".as_ptr() as *const c_char,
+            );
+            av_log_set_callback(Some(av_log_default_callback));
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(output.trim(), "WARN ffmpeg: disk full");
+    }
+}
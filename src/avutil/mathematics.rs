@@ -0,0 +1,31 @@
+use std::cmp::Ordering;
+
+use {av_compare_mod, av_compare_ts, AVRational};
+
+/// Compares `ts_a` in time base `tb_a` with `ts_b` in time base `tb_b`
+/// without loss of precision, as `av_compare_ts` does.
+pub fn compare_ts(ts_a: i64, tb_a: AVRational, ts_b: i64, tb_b: AVRational) -> Ordering {
+    unsafe { av_compare_ts(ts_a, tb_a, ts_b, tb_b).cmp(&0) }
+}
+
+/// Compares two timestamps that wrap around at `modulus`, which must be a
+/// power of two, as `av_compare_mod` does.
+///
+/// The result is negative if `a` is before `b`, positive if it is after it
+/// and 0 if they are equal modulo `modulus`.
+pub fn compare_mod(a: u64, b: u64, modulus: u64) -> i64 {
+    unsafe { av_compare_mod(a, b, modulus) }
+}
+
+// av_sat_add32 and av_sat_add64 are static inline in libavutil/common.h, so
+// bindgen doesn't generate them.
+
+#[inline(always)]
+pub fn sat_add32(a: i32, b: i32) -> i32 {
+    a.saturating_add(b)
+}
+
+#[inline(always)]
+pub fn sat_add64(a: i64, b: i64) -> i64 {
+    a.saturating_add(b)
+}
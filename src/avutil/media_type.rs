@@ -0,0 +1,81 @@
+use std::convert::TryFrom;
+use std::ffi::CStr;
+use std::fmt;
+use std::str::FromStr;
+
+use libc::EINVAL;
+use {av_get_media_type_string, AVMediaType};
+
+use super::{FFmpegError, AVERROR};
+
+// Shorthands for the variants, matching the C names.
+pub const AVMEDIA_TYPE_UNKNOWN: AVMediaType = AVMediaType::AVMEDIA_TYPE_UNKNOWN;
+pub const AVMEDIA_TYPE_VIDEO: AVMediaType = AVMediaType::AVMEDIA_TYPE_VIDEO;
+pub const AVMEDIA_TYPE_AUDIO: AVMediaType = AVMediaType::AVMEDIA_TYPE_AUDIO;
+pub const AVMEDIA_TYPE_DATA: AVMediaType = AVMediaType::AVMEDIA_TYPE_DATA;
+pub const AVMEDIA_TYPE_SUBTITLE: AVMediaType = AVMediaType::AVMEDIA_TYPE_SUBTITLE;
+pub const AVMEDIA_TYPE_ATTACHMENT: AVMediaType = AVMediaType::AVMEDIA_TYPE_ATTACHMENT;
+
+/// `"video"`, `"audio"`, ... or `"unknown"`.
+pub fn media_type_string(type_: AVMediaType) -> &'static str {
+    unsafe {
+        let name = av_get_media_type_string(type_);
+        if name.is_null() {
+            return "unknown";
+        }
+
+        CStr::from_ptr(name).to_str().unwrap_or("unknown")
+    }
+}
+
+impl fmt::Display for AVMediaType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(media_type_string(*self))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for AVMediaType {
+    type Error = FFmpegError;
+
+    fn try_from(name: &'a str) -> Result<Self, Self::Error> {
+        match name {
+            "video" => Ok(AVMEDIA_TYPE_VIDEO),
+            "audio" => Ok(AVMEDIA_TYPE_AUDIO),
+            "data" => Ok(AVMEDIA_TYPE_DATA),
+            "subtitle" => Ok(AVMEDIA_TYPE_SUBTITLE),
+            "attachment" => Ok(AVMEDIA_TYPE_ATTACHMENT),
+            _ => Err(FFmpegError::Other(AVERROR(EINVAL))),
+        }
+    }
+}
+
+impl FromStr for AVMediaType {
+    type Err = FFmpegError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        AVMediaType::try_from(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        for &(type_, name) in &[
+            (AVMEDIA_TYPE_VIDEO, "video"),
+            (AVMEDIA_TYPE_AUDIO, "audio"),
+            (AVMEDIA_TYPE_DATA, "data"),
+            (AVMEDIA_TYPE_SUBTITLE, "subtitle"),
+            (AVMEDIA_TYPE_ATTACHMENT, "attachment"),
+        ] {
+            assert_eq!(media_type_string(type_), name);
+            assert_eq!(type_.to_string(), name);
+            assert_eq!(name.parse::<AVMediaType>().unwrap(), type_);
+        }
+
+        assert_eq!(media_type_string(AVMEDIA_TYPE_UNKNOWN), "unknown");
+        assert!(AVMediaType::try_from("unknown").is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::slice;
+
+use libc::c_void;
+use {av_calloc, av_free, av_malloc, av_mallocz};
+
+/// Value or slice allocated with FFmpeg's aligned allocator and released
+/// with `av_free`, e.g. to hand over to APIs that take ownership of
+/// `av_malloc`ed memory via `into_raw`.
+pub struct AVAlloc<T: ?Sized>(*mut T);
+
+// SAFETY: AVAlloc uniquely owns its allocation, like Box.
+unsafe impl<T: ?Sized + Send> Send for AVAlloc<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for AVAlloc<T> {}
+
+// av_malloc aligns to 16, 32 or 64 bytes depending on which SIMD extensions
+// libavutil was built with; only the smallest of those can be relied on.
+const AV_MALLOC_MIN_ALIGN: usize = 16;
+
+impl<T> AVAlloc<T> {
+    /// `None` if the allocation fails or `T` needs more than 16-byte
+    /// alignment, which `av_malloc` doesn't guarantee.
+    pub fn new(value: T) -> Option<Self> {
+        if mem::align_of::<T>() > AV_MALLOC_MIN_ALIGN {
+            return None;
+        }
+
+        unsafe {
+            let ptr = av_malloc(mem::size_of::<T>() as _) as *mut T;
+            if ptr.is_null() {
+                return None;
+            }
+            ptr::write(ptr, value);
+
+            Some(AVAlloc(ptr))
+        }
+    }
+
+    /// `av_mallocz`, the all-zero bit pattern has to be a valid `T`. Same
+    /// alignment limit as `new`.
+    pub unsafe fn new_zeroed() -> Option<Self> {
+        if mem::align_of::<T>() > AV_MALLOC_MIN_ALIGN {
+            return None;
+        }
+
+        let ptr = av_mallocz(mem::size_of::<T>() as _) as *mut T;
+        if ptr.is_null() {
+            return None;
+        }
+
+        Some(AVAlloc(ptr))
+    }
+}
+
+impl<T: Clone> AVAlloc<[T]> {
+    /// `n` clones of `value`; `None` if the allocation fails, its size
+    /// overflows or `T` needs more than 16-byte alignment.
+    pub fn new_array(n: usize, value: T) -> Option<Self> {
+        if mem::align_of::<T>() > AV_MALLOC_MIN_ALIGN {
+            return None;
+        }
+
+        unsafe {
+            let ptr = av_calloc(n as _, mem::size_of::<T>() as _) as *mut T;
+            if ptr.is_null() {
+                return None;
+            }
+            for i in 0..n {
+                ptr::write(ptr.add(i), value.clone());
+            }
+
+            Some(AVAlloc(ptr::slice_from_raw_parts_mut(ptr, n)))
+        }
+    }
+}
+
+impl<T: ?Sized> AVAlloc<T> {
+    pub fn as_ptr(&self) -> *const T {
+        self.0
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.0
+    }
+
+    /// Releases ownership; the memory has to be freed with `av_free` (after
+    /// dropping the contents, if needed).
+    pub fn into_raw(self) -> *mut T {
+        let ptr = self.0;
+        mem::forget(self);
+        ptr
+    }
+}
+
+impl<T: ?Sized> Deref for AVAlloc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.0 }
+    }
+}
+
+impl<T: ?Sized> DerefMut for AVAlloc<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.0 }
+    }
+}
+
+impl<T: ?Sized> Drop for AVAlloc<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.0);
+            av_free(self.0 as *mut c_void);
+        }
+    }
+}
+
+/// Zero-initialized byte buffer from `av_mallocz`, aligned for SIMD use as
+/// frame data.
+pub struct AlignedBuffer {
+    ptr: *mut u8,
+    len: usize,
+}
+
+// SAFETY: AlignedBuffer uniquely owns its allocation, like Vec<u8>.
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
+
+impl AlignedBuffer {
+    pub fn alloc(size: usize) -> Option<Self> {
+        let ptr = unsafe { av_mallocz(size as _) as *mut u8 };
+        if ptr.is_null() {
+            return None;
+        }
+
+        Some(AlignedBuffer { ptr, len: size })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+
+    /// Releases ownership; the memory has to be freed with `av_free`.
+    pub fn into_raw(self) -> *mut u8 {
+        let ptr = self.ptr;
+        mem::forget(self);
+        ptr
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            av_free(self.ptr as *mut c_void);
+        }
+    }
+}
@@ -1,9 +1,16 @@
 #[macro_use]
 mod macros;
 
+#[cfg(test)]
+pub(crate) mod test_util;
+
 mod error;
 pub use self::error::*;
 
+#[macro_use]
+mod assert;
+pub use self::assert::*;
+
 mod util;
 pub use self::util::*;
 
@@ -12,3 +19,127 @@ pub use self::rational::*;
 
 mod pixfmt;
 pub use self::pixfmt::*;
+
+#[cfg(feature = "ffmpeg_5_1")]
+mod channel_layout;
+#[cfg(feature = "ffmpeg_5_1")]
+pub use self::channel_layout::*;
+
+mod hash;
+pub use self::hash::*;
+
+mod base64;
+pub use self::base64::*;
+
+mod compress;
+pub use self::compress::*;
+
+mod lfg;
+pub use self::lfg::*;
+
+mod crypto;
+pub use self::crypto::*;
+
+mod aes;
+pub use self::aes::*;
+
+mod eval;
+pub use self::eval::*;
+
+mod parseutils;
+pub use self::parseutils::*;
+
+mod hash_algo;
+pub use self::hash_algo::*;
+
+mod random;
+pub use self::random::*;
+
+mod display;
+pub use self::display::*;
+
+mod audio_meta;
+pub use self::audio_meta::*;
+
+#[cfg(feature = "ffmpeg_5_1")]
+mod fifo;
+#[cfg(feature = "ffmpeg_5_1")]
+pub use self::fifo::*;
+
+mod motion_vector;
+pub use self::motion_vector::*;
+
+mod stereo3d;
+pub use self::stereo3d::*;
+
+#[cfg(feature = "ffmpeg_4_0")]
+mod hwcontext;
+#[cfg(feature = "ffmpeg_4_0")]
+pub use self::hwcontext::*;
+
+mod pixdesc;
+pub use self::pixdesc::*;
+
+mod hmac;
+pub use self::hmac::*;
+
+#[cfg(feature = "hkdf")]
+mod hkdf;
+#[cfg(feature = "hkdf")]
+pub use self::hkdf::*;
+
+mod thread_message;
+pub use self::thread_message::*;
+
+mod avstring;
+pub use self::avstring::*;
+
+mod mem;
+pub use self::mem::*;
+
+mod log;
+pub use self::log::*;
+
+#[cfg(any(feature = "tracing", feature = "log"))]
+mod log_bridge;
+#[cfg(any(feature = "tracing", feature = "log"))]
+pub use self::log_bridge::*;
+
+mod imgutils;
+pub use self::imgutils::*;
+
+mod dict;
+pub use self::dict::*;
+
+mod cpu;
+pub use self::cpu::*;
+
+mod opt;
+pub use self::opt::*;
+
+mod file;
+pub use self::file::*;
+
+mod avframe;
+pub use self::avframe::*;
+
+mod tempfile;
+pub use self::tempfile::*;
+
+mod media_type;
+pub use self::media_type::*;
+
+mod samples;
+pub use self::samples::*;
+
+mod sample_size;
+pub use self::sample_size::*;
+
+mod picture_type;
+pub use self::picture_type::*;
+
+mod fourcc;
+pub use self::fourcc::*;
+
+mod mathematics;
+pub use self::mathematics::*;
@@ -0,0 +1,109 @@
+use std::mem;
+use std::slice;
+
+use {av_frame_get_side_data, AVFrame, AVFrameSideDataType, AVMotionVector};
+
+/// The `AV_FRAME_DATA_MOTION_VECTORS` side data of `frame`, empty if there is
+/// none; it is only valid for as long as the side data is attached to the
+/// frame.
+pub unsafe fn motion_vectors_from_frame<'a>(frame: *const AVFrame) -> &'a [AVMotionVector] {
+    let side_data =
+        av_frame_get_side_data(frame, AVFrameSideDataType::AV_FRAME_DATA_MOTION_VECTORS);
+    if side_data.is_null() || (*side_data).data.is_null() {
+        return &[];
+    }
+
+    // size is an int before FFmpeg 5.0
+    #[allow(clippy::unnecessary_cast)]
+    let size = (*side_data).size as usize;
+
+    slice::from_raw_parts(
+        (*side_data).data as *const AVMotionVector,
+        size / mem::size_of::<AVMotionVector>(),
+    )
+}
+
+/// Owned copy of an `AVMotionVector`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MotionVector {
+    /// Negative if the source is in the past, positive if in the future.
+    pub source: i32,
+    pub w: u8,
+    pub h: u8,
+    pub src_x: i16,
+    pub src_y: i16,
+    pub dst_x: i16,
+    pub dst_y: i16,
+    pub flags: u64,
+    pub motion_x: i32,
+    pub motion_y: i32,
+    pub motion_scale: u16,
+}
+
+impl From<AVMotionVector> for MotionVector {
+    fn from(mv: AVMotionVector) -> Self {
+        MotionVector {
+            source: mv.source,
+            w: mv.w,
+            h: mv.h,
+            src_x: mv.src_x,
+            src_y: mv.src_y,
+            dst_x: mv.dst_x,
+            dst_y: mv.dst_y,
+            flags: mv.flags,
+            motion_x: mv.motion_x,
+            motion_y: mv.motion_y,
+            motion_scale: mv.motion_scale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+    use {av_frame_alloc, av_frame_free, av_frame_new_side_data};
+
+    #[test]
+    fn layout() {
+        // the C layout itself is checked by the bindgen layout tests
+        assert_eq!(mem::size_of::<AVMotionVector>(), 40);
+    }
+
+    #[test]
+    fn side_data() {
+        unsafe {
+            let mut frame = av_frame_alloc();
+            assert!(motion_vectors_from_frame(frame).is_empty());
+
+            let side_data = av_frame_new_side_data(
+                frame,
+                AVFrameSideDataType::AV_FRAME_DATA_MOTION_VECTORS,
+                (2 * mem::size_of::<AVMotionVector>()) as _,
+            );
+            assert!(!side_data.is_null());
+
+            let data = (*side_data).data as *mut AVMotionVector;
+            for i in 0..2 {
+                let mut mv: AVMotionVector = mem::zeroed();
+                mv.source = -1;
+                mv.w = 16;
+                mv.h = 16;
+                mv.dst_x = 16 * i as i16;
+                mv.motion_x = 4 * i as i32;
+                mv.motion_scale = 4;
+                ptr::write(data.add(i), mv);
+            }
+
+            let mvs: Vec<MotionVector> = motion_vectors_from_frame(frame)
+                .iter()
+                .map(|&mv| mv.into())
+                .collect();
+            assert_eq!(mvs.len(), 2);
+            assert_eq!(mvs[0].source, -1);
+            assert_eq!((mvs[1].w, mvs[1].dst_x, mvs[1].motion_x), (16, 16, 4));
+
+            av_frame_free(&mut frame);
+        }
+    }
+}
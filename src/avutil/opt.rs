@@ -0,0 +1,110 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use libc::{c_char, c_int, c_void};
+use {av_opt_find, av_opt_next, AVOption, AVOptionType};
+
+// Options live in the static tables referenced by AVClass, so the references
+// handed out below are 'static.
+
+unsafe fn static_str(s: *const c_char) -> Option<&'static str> {
+    if s.is_null() {
+        return None;
+    }
+
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// Iterator over the options of an AVClass-enabled object, not including
+/// those of its children.
+pub struct Options {
+    obj: *const c_void,
+    prev: *const AVOption,
+}
+
+impl Iterator for Options {
+    type Item = &'static AVOption;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            self.prev = av_opt_next(self.obj, self.prev);
+            self.prev.as_ref()
+        }
+    }
+}
+
+/// `obj` is a pointer to a struct whose first member is an `AVClass`
+/// pointer, or to the `AVClass` pointer itself.
+pub unsafe fn options(obj: *const c_void) -> Options {
+    Options {
+        obj,
+        prev: ptr::null(),
+    }
+}
+
+pub unsafe fn find_option(
+    obj: *const c_void,
+    name: &str,
+    unit: Option<&str>,
+    opt_flags: c_int,
+    search_flags: c_int,
+) -> Option<&'static AVOption> {
+    let name = CString::new(name).ok()?;
+    let unit = match unit {
+        Some(unit) => Some(CString::new(unit).ok()?),
+        None => None,
+    };
+
+    av_opt_find(
+        obj as *mut c_void,
+        name.as_ptr(),
+        unit.as_ref().map_or(ptr::null(), |unit| unit.as_ptr()),
+        opt_flags,
+        search_flags,
+    )
+    .as_ref()
+}
+
+pub unsafe fn opt_name(opt: *const AVOption) -> &'static str {
+    static_str((*opt).name).unwrap_or("")
+}
+
+pub unsafe fn opt_help(opt: *const AVOption) -> Option<&'static str> {
+    static_str((*opt).help)
+}
+
+pub unsafe fn opt_type(opt: *const AVOption) -> AVOptionType {
+    (*opt).type_
+}
+
+pub unsafe fn opt_unit(opt: *const AVOption) -> Option<&'static str> {
+    static_str((*opt).unit)
+}
+
+#[cfg(all(test, feature = "avcodec"))]
+mod tests {
+    use super::*;
+    use {avcodec_alloc_context3, avcodec_free_context};
+
+    #[test]
+    fn codec_context_options() {
+        unsafe {
+            let mut ctx = avcodec_alloc_context3(ptr::null());
+            assert!(!ctx.is_null());
+
+            let ints = options(ctx as *const c_void)
+                .filter(|opt| opt_type(*opt) == AVOptionType::AV_OPT_TYPE_INT)
+                .count();
+            assert!(ints > 0);
+
+            let threads = find_option(ctx as *const c_void, "threads", None, 0, 0).unwrap();
+            assert_eq!(opt_name(threads), "threads");
+            assert_eq!(opt_type(threads), AVOptionType::AV_OPT_TYPE_INT);
+            assert_eq!(opt_unit(threads), Some("threads"));
+            assert!(opt_help(threads).is_some());
+            assert!(find_option(ctx as *const c_void, "no_such_option", None, 0, 0).is_none());
+
+            avcodec_free_context(&mut ctx);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::ffi::CString;
+use std::ptr;
+
+use libc::c_int;
+use {av_parse_color, av_parse_ratio, av_parse_time, av_small_strptime, tm, AVRational};
+
+use super::error::check;
+use super::FFmpegError;
+
+/// Parses a date (`"2024-01-31T12:00:00Z"`, `"now"`, ...) or, if
+/// `is_duration` is set, a duration (`"00:01:23.456"`, `"83.456"`, `"-5s"`,
+/// ...), returning microseconds since the epoch or the duration in
+/// microseconds respectively.
+pub fn parse_time(timestr: &str, is_duration: bool) -> Result<i64, FFmpegError> {
+    let timestr = CString::new(timestr)?;
+    let mut time = 0;
+
+    unsafe {
+        check(av_parse_time(
+            &mut time,
+            timestr.as_ptr(),
+            is_duration as c_int,
+        ))?;
+    }
+
+    Ok(time)
+}
+
+/// Parses a ratio (`"16:9"`, `"1.7777"`, an expression, ...), with numerator
+/// and denominator no larger than `max`.
+pub fn parse_ratio(str: &str, max: i32) -> Result<AVRational, FFmpegError> {
+    let str = CString::new(str)?;
+    let mut q = AVRational { num: 0, den: 1 };
+
+    unsafe {
+        check(av_parse_ratio(
+            &mut q,
+            str.as_ptr(),
+            max,
+            0,
+            ptr::null_mut(),
+        ))?;
+    }
+
+    Ok(q)
+}
+
+/// Parses a color name or `#RRGGBB[AA]`/`0xRRGGBB[AA]` value (optionally
+/// followed by `@alpha`) into RGBA.
+pub fn parse_color(color_string: &str) -> Result<[u8; 4], FFmpegError> {
+    let color_string = CString::new(color_string)?;
+    let mut rgba = [0u8; 4];
+
+    unsafe {
+        check(av_parse_color(
+            rgba.as_mut_ptr(),
+            color_string.as_ptr(),
+            -1,
+            ptr::null_mut(),
+        ))?;
+    }
+
+    Ok(rgba)
+}
+
+/// Simplified `strptime`, see `av_small_strptime` for the supported
+/// conversions. Returns the unparsed rest of `p` on success.
+pub fn small_strptime<'a>(p: &'a str, fmt: &str, time: &mut tm) -> Option<&'a str> {
+    let input = CString::new(p).ok()?;
+    let fmt = CString::new(fmt).ok()?;
+
+    unsafe {
+        let end = av_small_strptime(input.as_ptr(), fmt.as_ptr(), time);
+        if end.is_null() {
+            return None;
+        }
+
+        p.get(end.offset_from(input.as_ptr()) as usize..)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_time("00:01:23.456", true).unwrap(), 83_456_000);
+        assert_eq!(parse_time("83.456", true).unwrap(), 83_456_000);
+        assert_eq!(parse_time("-5", true).unwrap(), -5_000_000);
+        assert!(parse_time("not a time", true).is_err());
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(
+            parse_time("2000-01-01T00:00:00Z", false).unwrap(),
+            946_684_800_000_000
+        );
+    }
+
+    #[test]
+    fn ratios() {
+        let q = parse_ratio("16:9", 255).unwrap();
+        assert_eq!((q.num, q.den), (16, 9));
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(parse_color("red").unwrap(), [255, 0, 0, 255]);
+        assert_eq!(parse_color("#FF8000").unwrap(), [255, 128, 0, 255]);
+        assert_eq!(parse_color("#11223344").unwrap(), [0x11, 0x22, 0x33, 0x44]);
+        assert_eq!(parse_color("0x00ff00@0.5").unwrap(), [0, 255, 0, 127]);
+        assert!(parse_color("no-such-color").is_err());
+    }
+
+    #[test]
+    fn strptime() {
+        let mut time: tm = unsafe { ::std::mem::zeroed() };
+        assert_eq!(
+            small_strptime("12:34:56 rest", "%H:%M:%S", &mut time),
+            Some(" rest")
+        );
+        assert_eq!((time.tm_hour, time.tm_min, time.tm_sec), (12, 34, 56));
+        assert_eq!(small_strptime("12:34", "%H:%M:%S", &mut time), None);
+    }
+}
@@ -0,0 +1,96 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use libc::EINVAL;
+use {av_get_picture_type_char, AVPictureType};
+
+use super::{FFmpegError, AVERROR};
+
+/// `'I'`, `'P'`, `'B'`, `'S'` (S(GMC)-VOP), `'i'` (SI), `'p'` (SP), `'b'`
+/// (BI), or `'?'` for `AV_PICTURE_TYPE_NONE`.
+pub fn picture_type_char(pict_type: AVPictureType) -> char {
+    unsafe { av_get_picture_type_char(pict_type) as u8 as char }
+}
+
+/// Whether frames of this type are references by definition, i.e. I and P
+/// frames and their switching variants. B frames may still be used as
+/// references by some codecs, such as H.264 with B-pyramids.
+pub fn is_reference_frame(pict_type: AVPictureType) -> bool {
+    matches!(
+        pict_type,
+        AVPictureType::AV_PICTURE_TYPE_I
+            | AVPictureType::AV_PICTURE_TYPE_P
+            | AVPictureType::AV_PICTURE_TYPE_SI
+            | AVPictureType::AV_PICTURE_TYPE_SP
+    )
+}
+
+impl fmt::Display for AVPictureType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", picture_type_char(*self))
+    }
+}
+
+impl TryFrom<char> for AVPictureType {
+    type Error = FFmpegError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'I' => Ok(AVPictureType::AV_PICTURE_TYPE_I),
+            'P' => Ok(AVPictureType::AV_PICTURE_TYPE_P),
+            'B' => Ok(AVPictureType::AV_PICTURE_TYPE_B),
+            'S' => Ok(AVPictureType::AV_PICTURE_TYPE_S),
+            'i' => Ok(AVPictureType::AV_PICTURE_TYPE_SI),
+            'p' => Ok(AVPictureType::AV_PICTURE_TYPE_SP),
+            'b' => Ok(AVPictureType::AV_PICTURE_TYPE_BI),
+            '?' => Ok(AVPictureType::AV_PICTURE_TYPE_NONE),
+            _ => Err(FFmpegError::Other(AVERROR(EINVAL))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AVPictureType::*;
+
+    const TYPES: &[(AVPictureType, char)] = &[
+        (AV_PICTURE_TYPE_NONE, '?'),
+        (AV_PICTURE_TYPE_I, 'I'),
+        (AV_PICTURE_TYPE_P, 'P'),
+        (AV_PICTURE_TYPE_B, 'B'),
+        (AV_PICTURE_TYPE_S, 'S'),
+        (AV_PICTURE_TYPE_SI, 'i'),
+        (AV_PICTURE_TYPE_SP, 'p'),
+        (AV_PICTURE_TYPE_BI, 'b'),
+    ];
+
+    #[test]
+    fn chars() {
+        for &(pict_type, c) in TYPES {
+            assert_eq!(picture_type_char(pict_type), c);
+            assert_eq!(pict_type.to_string(), c.to_string());
+            assert_eq!(AVPictureType::try_from(c).unwrap(), pict_type);
+        }
+
+        assert!(AVPictureType::try_from('X').is_err());
+    }
+
+    #[test]
+    fn reference_frames() {
+        let references: Vec<_> = TYPES
+            .iter()
+            .map(|t| t.0)
+            .filter(|&t| is_reference_frame(t))
+            .collect();
+        assert_eq!(
+            references,
+            [
+                AV_PICTURE_TYPE_I,
+                AV_PICTURE_TYPE_P,
+                AV_PICTURE_TYPE_SI,
+                AV_PICTURE_TYPE_SP
+            ]
+        );
+    }
+}
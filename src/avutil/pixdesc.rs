@@ -0,0 +1,74 @@
+use std::ptr;
+
+use {
+    av_pix_fmt_count_planes, av_pix_fmt_desc_get, av_pix_fmt_desc_get_id, av_pix_fmt_desc_next,
+    AVPixFmtDescriptor, AVPixelFormat,
+};
+
+/// Iterator over all pixel format descriptors known to libavutil.
+pub struct PixFmtDescriptors(*const AVPixFmtDescriptor);
+
+impl Iterator for PixFmtDescriptors {
+    type Item = (AVPixelFormat, &'static AVPixFmtDescriptor);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            self.0 = av_pix_fmt_desc_next(self.0);
+            if self.0.is_null() {
+                return None;
+            }
+
+            Some((av_pix_fmt_desc_get_id(self.0), &*self.0))
+        }
+    }
+}
+
+pub fn pix_fmt_descriptors() -> PixFmtDescriptors {
+    PixFmtDescriptors(ptr::null())
+}
+
+pub fn pix_fmt_descriptor(fmt: AVPixelFormat) -> Option<&'static AVPixFmtDescriptor> {
+    unsafe { av_pix_fmt_desc_get(fmt).as_ref() }
+}
+
+/// Number of planes in `fmt`, 0 if it is not a valid pixel format.
+pub fn pix_fmt_count_planes(fmt: AVPixelFormat) -> u32 {
+    let planes = unsafe { av_pix_fmt_count_planes(fmt) };
+    if planes < 0 {
+        0
+    } else {
+        planes as u32
+    }
+}
+
+/// `AV_PIX_FMT_NONE` if `desc` is not one of libavutil's descriptors.
+pub unsafe fn pix_fmt_from_descriptor(desc: *const AVPixFmtDescriptor) -> AVPixelFormat {
+    av_pix_fmt_desc_get_id(desc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn count_planes() {
+        assert_eq!(pix_fmt_count_planes(AVPixelFormat::AV_PIX_FMT_YUV420P), 3);
+        assert_eq!(pix_fmt_count_planes(AVPixelFormat::AV_PIX_FMT_RGB24), 1);
+        assert_eq!(pix_fmt_count_planes(AVPixelFormat::AV_PIX_FMT_NONE), 0);
+    }
+
+    #[test]
+    fn descriptors() {
+        let desc = pix_fmt_descriptor(AVPixelFormat::AV_PIX_FMT_YUV420P).unwrap();
+        assert_eq!(unsafe { CStr::from_ptr(desc.name) }.to_bytes(), b"yuv420p");
+        assert_eq!(desc.nb_components, 3);
+        assert_eq!((desc.log2_chroma_w, desc.log2_chroma_h), (1, 1));
+        assert!(pix_fmt_descriptor(AVPixelFormat::AV_PIX_FMT_NONE).is_none());
+
+        for (fmt, desc) in pix_fmt_descriptors() {
+            assert_eq!(unsafe { pix_fmt_from_descriptor(desc) }, fmt);
+        }
+        assert!(pix_fmt_descriptors().any(|(fmt, _)| fmt == AVPixelFormat::AV_PIX_FMT_RGB24));
+    }
+}
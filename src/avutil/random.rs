@@ -0,0 +1,116 @@
+use std::hash::Hasher;
+
+use libc::c_void;
+use {
+    av_free, av_get_random_seed, av_murmur3_alloc, av_murmur3_final, av_murmur3_init_seeded,
+    av_murmur3_update, AVMurMur3,
+};
+
+/// Seed taken from the OS entropy source (`/dev/urandom`, `BCryptGenRandom`,
+/// ...), falling back to a timer based one.
+pub fn random_seed() -> u32 {
+    unsafe { av_get_random_seed() }
+}
+
+/// Streaming MurmurHash3 (x64, 128 bit) context.
+pub struct Murmur3(*mut AVMurMur3);
+
+// SAFETY: AVMurMur3 has no thread affinity and is only mutated through
+// &mut self; see the safety_notes module.
+unsafe impl Send for Murmur3 {}
+
+impl Murmur3 {
+    pub fn new_seeded(seed: u64) -> Self {
+        unsafe {
+            let ctx = av_murmur3_alloc();
+            assert!(!ctx.is_null(), "av_murmur3_alloc failed");
+            av_murmur3_init_seeded(ctx, seed);
+            Murmur3(ctx)
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        unsafe {
+            av_murmur3_update(self.0, data.as_ptr(), data.len() as _);
+        }
+    }
+
+    pub fn finalize(self) -> [u8; 16] {
+        let mut digest = [0u8; 16];
+        unsafe {
+            av_murmur3_final(self.0, digest.as_mut_ptr());
+        }
+        digest
+    }
+}
+
+impl Drop for Murmur3 {
+    fn drop(&mut self) {
+        unsafe {
+            av_free(self.0 as *mut c_void);
+        }
+    }
+}
+
+/// `Hasher` backed by `Murmur3`, returning the low 64 bits of the digest.
+///
+/// `Hasher::finish` takes `&self` and may be called repeatedly, while
+/// `av_murmur3_final` consumes the (opaque, thus not clonable) context state,
+/// so the written bytes are kept around and hashed on each `finish`.
+#[derive(Clone, Debug, Default)]
+pub struct Murmur3Hasher {
+    seed: u64,
+    data: Vec<u8>,
+}
+
+impl Murmur3Hasher {
+    pub fn new_seeded(seed: u64) -> Self {
+        Murmur3Hasher {
+            seed,
+            data: Vec::new(),
+        }
+    }
+}
+
+impl Hasher for Murmur3Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut murmur3 = Murmur3::new_seeded(self.seed);
+        murmur3.update(&self.data);
+
+        let digest = murmur3.finalize();
+        let mut low = [0u8; 8];
+        low.copy_from_slice(&digest[..8]);
+        u64::from_le_bytes(low)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avutil::test_util::hex;
+
+    #[test]
+    fn murmur3_empty() {
+        assert_eq!(Murmur3::new_seeded(0).finalize(), [0u8; 16]);
+    }
+
+    #[test]
+    fn murmur3_abc() {
+        let mut murmur3 = Murmur3::new_seeded(0);
+        murmur3.update(b"a");
+        murmur3.update(b"bc");
+        assert_eq!(hex(&murmur3.finalize()), "6778ad3f3f3f96b4522dca264174a23b");
+    }
+
+    #[test]
+    fn hasher() {
+        let mut hasher = Murmur3Hasher::new_seeded(0);
+        hasher.write(b"abc");
+        assert_eq!(hasher.finish(), 0xb496_3f3f_3fad_7867);
+        assert_eq!(hasher.finish(), 0xb496_3f3f_3fad_7867);
+    }
+}
@@ -1,5 +1,5 @@
 use libc::{c_double, c_int};
-use AVRational;
+use {av_find_nearest_q_idx, av_nearer_q, av_q2intfloat, AVRational};
 
 #[inline(always)]
 pub unsafe fn av_make_q(num: c_int, den: c_int) -> AVRational {
@@ -33,3 +33,92 @@ pub unsafe fn av_inv_q(q: AVRational) -> AVRational {
         den: q.num,
     }
 }
+
+/// 1 if `q1` is nearer to `q` than `q2`, -1 if `q2` is, 0 if they are
+/// equidistant, as returned by `av_nearer_q`.
+pub fn nearer_q(q: AVRational, q1: AVRational, q2: AVRational) -> c_int {
+    unsafe { av_nearer_q(q, q1, q2) }
+}
+
+/// Index of the entry of `q_list` nearest to `q`.
+///
+/// FFmpeg stops at the first entry with a zero denominator (`0/0`, but also
+/// e.g. `1/0`), so that entry and everything after it is ignored. `None` is
+/// returned if that leaves no entries.
+pub fn find_nearest_q_idx(q: AVRational, q_list: &[AVRational]) -> Option<usize> {
+    let len = q_list
+        .iter()
+        .position(|entry| entry.den == 0)
+        .unwrap_or(q_list.len());
+    if len == 0 {
+        return None;
+    }
+
+    let mut terminated = q_list[..len].to_vec();
+    terminated.push(AVRational { num: 0, den: 0 });
+
+    unsafe { Some(av_find_nearest_q_idx(q, terminated.as_ptr()) as usize) }
+}
+
+/// Bit pattern of `q` as an IEEE 754 single precision float, computed
+/// without going through floating point.
+pub fn q2intfloat(q: AVRational) -> u32 {
+    unsafe { av_q2intfloat(q) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(num: c_int, den: c_int) -> AVRational {
+        AVRational { num, den }
+    }
+
+    #[test]
+    fn nearer() {
+        assert_eq!(nearer_q(q(1, 2), q(1, 3), q(2, 3)), 0);
+        assert_eq!(nearer_q(q(1, 2), q(1, 2), q(1, 3)), 1);
+        assert_eq!(nearer_q(q(1, 2), q(1, 3), q(1, 2)), -1);
+        assert_eq!(nearer_q(q(0, 1), q(-1, 3), q(1, 3)), 0);
+        assert_eq!(nearer_q(q(1, 2), q(2, 4), q(1, 2)), 0);
+    }
+
+    #[test]
+    fn nearest_idx() {
+        let rates = [q(24000, 1001), q(24, 1), q(25, 1), q(30000, 1001)];
+        assert_eq!(find_nearest_q_idx(q(25, 1), &rates), Some(2));
+        assert_eq!(find_nearest_q_idx(q(1, 1), &rates), Some(0));
+        assert_eq!(find_nearest_q_idx(q(60, 1), &rates), Some(3));
+
+        // the first of equidistant entries wins
+        assert_eq!(find_nearest_q_idx(q(1, 2), &[q(1, 3), q(2, 3)]), Some(0));
+        assert_eq!(find_nearest_q_idx(q(1, 2), &[q(2, 3), q(1, 3)]), Some(0));
+
+        assert_eq!(find_nearest_q_idx(q(1, 2), &[q(5, 1)]), Some(0));
+        assert_eq!(find_nearest_q_idx(q(1, 2), &[]), None);
+
+        // the search stops at the first zero denominator
+        assert_eq!(
+            find_nearest_q_idx(q(5, 1), &[q(1, 1), q(1, 0), q(5, 1)]),
+            Some(0)
+        );
+        assert_eq!(find_nearest_q_idx(q(5, 1), &[q(1, 0), q(5, 1)]), None);
+    }
+
+    #[test]
+    fn intfloat() {
+        for &(num, den) in &[(1, 1), (1, 2), (-3, 2), (1, 3), (3, -7), (30000, 1001)] {
+            assert_eq!(
+                q2intfloat(q(num, den)),
+                (num as f32 / den as f32).to_bits(),
+                "{}/{}",
+                num,
+                den
+            );
+        }
+
+        assert_eq!(q2intfloat(q(0, 1)), 0);
+        assert_eq!(q2intfloat(q(1, 0)), f32::INFINITY.to_bits());
+        assert!(f32::from_bits(q2intfloat(q(0, 0))).is_nan());
+    }
+}
@@ -0,0 +1,54 @@
+use libc::c_int;
+#[cfg(feature = "avcodec")]
+use AVCodecContext;
+use {av_get_alt_sample_fmt, av_get_bytes_per_sample, AVSampleFormat};
+
+/// Size of a single sample of one channel, 0 for `AV_SAMPLE_FMT_NONE` and
+/// unknown formats.
+pub fn bytes_per_sample(fmt: AVSampleFormat) -> u32 {
+    let bytes = unsafe { av_get_bytes_per_sample(fmt) };
+    if bytes > 0 {
+        bytes as u32
+    } else {
+        0
+    }
+}
+
+/// Bits per sample of the raw, uncompressed samples, 0 if unknown.
+///
+/// There is no `av_get_bits_per_raw_sample`, this reads the
+/// `bits_per_raw_sample` field of `ctx`, which must be valid.
+#[cfg(feature = "avcodec")]
+pub unsafe fn bits_per_raw_sample(ctx: *const AVCodecContext) -> c_int {
+    (*ctx).bits_per_raw_sample
+}
+
+/// Planar (`planar` true) or packed variant of `fmt`, `AV_SAMPLE_FMT_NONE`
+/// if `fmt` is invalid.
+pub fn alt_sample_fmt(fmt: AVSampleFormat, planar: bool) -> AVSampleFormat {
+    unsafe { av_get_alt_sample_fmt(fmt, planar as c_int) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AVSampleFormat::*;
+
+    #[test]
+    fn sizes() {
+        assert_eq!(bytes_per_sample(AV_SAMPLE_FMT_U8), 1);
+        assert_eq!(bytes_per_sample(AV_SAMPLE_FMT_S16), 2);
+        assert_eq!(bytes_per_sample(AV_SAMPLE_FMT_S32), 4);
+        assert_eq!(bytes_per_sample(AV_SAMPLE_FMT_FLTP), 4);
+        assert_eq!(bytes_per_sample(AV_SAMPLE_FMT_DBL), 8);
+        assert_eq!(bytes_per_sample(AV_SAMPLE_FMT_NONE), 0);
+    }
+
+    #[test]
+    fn alt_formats() {
+        assert_eq!(alt_sample_fmt(AV_SAMPLE_FMT_FLT, true), AV_SAMPLE_FMT_FLTP);
+        assert_eq!(alt_sample_fmt(AV_SAMPLE_FMT_FLTP, false), AV_SAMPLE_FMT_FLT);
+        assert_eq!(alt_sample_fmt(AV_SAMPLE_FMT_S16, false), AV_SAMPLE_FMT_S16);
+        assert_eq!(alt_sample_fmt(AV_SAMPLE_FMT_NONE, true), AV_SAMPLE_FMT_NONE);
+    }
+}
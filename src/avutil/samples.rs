@@ -0,0 +1,152 @@
+use std::ptr;
+
+use libc::{c_int, EINVAL};
+use {
+    av_sample_fmt_is_planar, av_samples_fill_arrays, av_samples_get_buffer_size,
+    av_samples_set_silence, AVSampleFormat,
+};
+
+use super::error::check;
+use super::{FFmpegError, AVERROR};
+
+/// Size in bytes of a buffer holding the given samples, all planes included.
+pub fn samples_get_buffer_size(
+    nb_channels: c_int,
+    nb_samples: c_int,
+    sample_fmt: AVSampleFormat,
+    align: c_int,
+) -> Result<usize, FFmpegError> {
+    let size = unsafe {
+        check(av_samples_get_buffer_size(
+            ptr::null_mut(),
+            nb_channels,
+            nb_samples,
+            sample_fmt,
+            align,
+        ))?
+    };
+    Ok(size as usize)
+}
+
+/// Sets up `audio_data` and `linesize` to point into `buf`, which must be at
+/// least `samples_get_buffer_size` bytes.
+///
+/// Planar formats need one pointer per channel, so at most 8 channels are
+/// supported for them; use `AVFrame::extended_data` for more.
+pub unsafe fn samples_fill_arrays(
+    audio_data: &mut [*mut u8; 8],
+    linesize: &mut c_int,
+    buf: *const u8,
+    nb_channels: c_int,
+    nb_samples: c_int,
+    sample_fmt: AVSampleFormat,
+    align: c_int,
+) -> Result<(), FFmpegError> {
+    if av_sample_fmt_is_planar(sample_fmt) != 0 && nb_channels as usize > audio_data.len() {
+        return Err(FFmpegError::Other(AVERROR(EINVAL)));
+    }
+
+    check(av_samples_fill_arrays(
+        audio_data.as_mut_ptr(),
+        linesize,
+        buf,
+        nb_channels,
+        nb_samples,
+        sample_fmt,
+        align,
+    ))?;
+    Ok(())
+}
+
+/// Fills `nb_samples` samples starting at `offset` with silence, which is
+/// not all zero bytes for unsigned formats.
+pub unsafe fn samples_set_silence(
+    audio_data: *const *mut u8,
+    offset: c_int,
+    nb_samples: c_int,
+    nb_channels: c_int,
+    sample_fmt: AVSampleFormat,
+) -> Result<(), FFmpegError> {
+    check(av_samples_set_silence(
+        audio_data,
+        offset,
+        nb_samples,
+        nb_channels,
+        sample_fmt,
+    ))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AVSampleFormat::{AV_SAMPLE_FMT_S16, AV_SAMPLE_FMT_S16P, AV_SAMPLE_FMT_U8};
+
+    fn silence(nb_channels: c_int, sample_fmt: AVSampleFormat) -> Vec<u8> {
+        // no alignment, so that there is no padding between the planes
+        let size = samples_get_buffer_size(nb_channels, 100, sample_fmt, 1).unwrap();
+        let mut buf = vec![0xaa; size];
+        let mut audio_data = [ptr::null_mut(); 8];
+        let mut linesize = 0;
+
+        unsafe {
+            samples_fill_arrays(
+                &mut audio_data,
+                &mut linesize,
+                buf.as_mut_ptr(),
+                nb_channels,
+                100,
+                sample_fmt,
+                1,
+            )
+            .unwrap();
+            samples_set_silence(audio_data.as_ptr(), 0, 100, nb_channels, sample_fmt).unwrap();
+        }
+        buf
+    }
+
+    #[test]
+    fn buffer_size() {
+        assert_eq!(
+            samples_get_buffer_size(2, 100, AV_SAMPLE_FMT_S16, 1).unwrap(),
+            400
+        );
+        assert!(samples_get_buffer_size(0, 100, AV_SAMPLE_FMT_S16, 1).is_err());
+    }
+
+    #[test]
+    fn signed_silence() {
+        let buf = silence(2, AV_SAMPLE_FMT_S16);
+        assert_eq!(buf.len(), 400);
+        assert!(buf.iter().all(|&b| b == 0));
+
+        let buf = silence(2, AV_SAMPLE_FMT_S16P);
+        assert_eq!(buf.len(), 400);
+        assert!(buf.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn unsigned_silence() {
+        let buf = silence(1, AV_SAMPLE_FMT_U8);
+        assert_eq!(buf.len(), 100);
+        assert!(buf.iter().all(|&b| b == 0x80));
+    }
+
+    #[test]
+    fn too_many_planes() {
+        let mut audio_data = [ptr::null_mut(); 8];
+        let mut linesize = 0;
+        let ret = unsafe {
+            samples_fill_arrays(
+                &mut audio_data,
+                &mut linesize,
+                ptr::null(),
+                9,
+                100,
+                AV_SAMPLE_FMT_S16P,
+                1,
+            )
+        };
+        assert!(ret.is_err());
+    }
+}
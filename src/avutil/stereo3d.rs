@@ -0,0 +1,112 @@
+use std::ffi::CStr;
+
+use libc::{c_uint, ENOMEM};
+use {
+    av_stereo3d_alloc, av_stereo3d_create_side_data, av_stereo3d_type_name, AVFrame,
+    AVFrameSideDataType, AVStereo3D, AVStereo3DType, AVStereo3DView,
+};
+
+use super::display::frame_side_data;
+use super::error::{FFmpegError, AVERROR};
+
+/// The `AV_FRAME_DATA_STEREO3D` side data of `frame`, if present; it is only
+/// valid for as long as the side data is attached to the frame.
+pub unsafe fn stereo3d_from_frame<'a>(frame: *const AVFrame) -> Option<&'a AVStereo3D> {
+    frame_side_data(frame, AVFrameSideDataType::AV_FRAME_DATA_STEREO3D)
+}
+
+/// Allocates a zeroed `AVStereo3D`, to be released with `av_free`.
+pub fn stereo3d_alloc() -> Result<*mut AVStereo3D, FFmpegError> {
+    let stereo = unsafe { av_stereo3d_alloc() };
+    if stereo.is_null() {
+        return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+    }
+
+    Ok(stereo)
+}
+
+/// Attaches new `AV_FRAME_DATA_STEREO3D` side data to `frame`; the returned
+/// struct is owned by the frame.
+pub unsafe fn stereo3d_create_side_data(
+    frame: *mut AVFrame,
+) -> Result<*mut AVStereo3D, FFmpegError> {
+    let stereo = av_stereo3d_create_side_data(frame);
+    if stereo.is_null() {
+        return Err(FFmpegError::Other(AVERROR(ENOMEM)));
+    }
+
+    Ok(stereo)
+}
+
+/// Human-readable name of a stereo 3D packing type, "unknown" if invalid.
+pub fn stereo3d_type_name(type_: AVStereo3DType) -> &'static str {
+    unsafe {
+        CStr::from_ptr(av_stereo3d_type_name(type_ as c_uint))
+            .to_str()
+            .unwrap_or("unknown")
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Stereo3DView {
+    LeftEye,
+    RightEye,
+    /// Both views packed into the frame (`AV_STEREO3D_VIEW_PACKED`).
+    Combined,
+    #[cfg(feature = "ffmpeg_7_1")]
+    Unspecified,
+}
+
+impl From<AVStereo3DView> for Stereo3DView {
+    fn from(view: AVStereo3DView) -> Self {
+        match view {
+            AVStereo3DView::AV_STEREO3D_VIEW_LEFT => Stereo3DView::LeftEye,
+            AVStereo3DView::AV_STEREO3D_VIEW_RIGHT => Stereo3DView::RightEye,
+            AVStereo3DView::AV_STEREO3D_VIEW_PACKED => Stereo3DView::Combined,
+            #[cfg(feature = "ffmpeg_7_1")]
+            AVStereo3DView::AV_STEREO3D_VIEW_UNSPEC => Stereo3DView::Unspecified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libc::c_void;
+    use {av_frame_alloc, av_frame_free, av_free};
+
+    #[test]
+    fn side_data() {
+        unsafe {
+            let mut frame = av_frame_alloc();
+            assert!(stereo3d_from_frame(frame).is_none());
+
+            let stereo = stereo3d_create_side_data(frame).unwrap();
+            (*stereo).type_ = AVStereo3DType::AV_STEREO3D_TOPBOTTOM;
+
+            let stereo = stereo3d_from_frame(frame).unwrap();
+            assert_eq!(stereo.type_, AVStereo3DType::AV_STEREO3D_TOPBOTTOM);
+            assert_eq!(stereo3d_type_name(stereo.type_), "top and bottom");
+
+            av_frame_free(&mut frame);
+        }
+    }
+
+    #[test]
+    fn alloc_and_names() {
+        let stereo = stereo3d_alloc().unwrap();
+        unsafe {
+            assert_eq!((*stereo).type_, AVStereo3DType::AV_STEREO3D_2D);
+            av_free(stereo as *mut c_void);
+        }
+
+        assert_eq!(
+            stereo3d_type_name(AVStereo3DType::AV_STEREO3D_SIDEBYSIDE),
+            "side by side"
+        );
+        assert_eq!(
+            Stereo3DView::from(AVStereo3DView::AV_STEREO3D_VIEW_RIGHT),
+            Stereo3DView::RightEye
+        );
+    }
+}
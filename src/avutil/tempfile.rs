@@ -0,0 +1,97 @@
+use std::ffi::{CStr, CString};
+use std::fs;
+#[cfg(unix)]
+use std::fs::File;
+#[cfg(unix)]
+use std::io;
+#[cfg(unix)]
+use std::os::unix::io::FromRawFd;
+use std::ptr;
+
+#[cfg(unix)]
+use libc::dup;
+use libc::{c_char, c_int, close};
+use {av_freep, av_tempfile};
+
+use super::error::check;
+use super::FFmpegError;
+
+/// Temporary file created with `av_tempfile`, which falls back to `tempnam`
+/// and friends where `mkstemp` is missing.
+///
+/// The descriptor is owned by this struct: it is closed, and the file
+/// removed, on drop. To use it through `std::fs::File`, get an independent
+/// descriptor with `try_clone_file` rather than passing `fd` to
+/// `from_raw_fd`, which would close it twice.
+#[derive(Debug)]
+pub struct AvTempFile {
+    pub fd: c_int,
+    pub path: String,
+}
+
+impl AvTempFile {
+    pub fn new(prefix: &str) -> Result<Self, FFmpegError> {
+        let prefix = CString::new(prefix)?;
+        let mut filename: *mut c_char = ptr::null_mut();
+
+        unsafe {
+            let ret = av_tempfile(prefix.as_ptr(), &mut filename, 0, ptr::null_mut());
+            // the filename is allocated even if opening the file failed
+            let path = if filename.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(filename).to_string_lossy().into_owned()
+            };
+            av_freep(&mut filename as *mut _ as *mut _);
+
+            let fd = check(ret)?;
+            Ok(AvTempFile { fd, path })
+        }
+    }
+
+    /// `File` on a duplicate of the descriptor, sharing its file offset.
+    #[cfg(unix)]
+    pub fn try_clone_file(&self) -> io::Result<File> {
+        unsafe {
+            let fd = dup(self.fd);
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(File::from_raw_fd(fd))
+        }
+    }
+}
+
+impl Drop for AvTempFile {
+    fn drop(&mut self) {
+        unsafe {
+            close(self.fd);
+        }
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::io::{Read, Seek, SeekFrom, Write};
+    use std::path::Path;
+
+    #[test]
+    fn write_and_read_back() {
+        let tmp = AvTempFile::new("ffmpeg-sys").unwrap();
+        let path = tmp.path.clone();
+        assert!(Path::new(&path).exists());
+
+        let mut file = tmp.try_clone_file().unwrap();
+        file.write_all(b"hello").unwrap();
+        file.seek(SeekFrom::Start(0)).unwrap();
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "hello");
+
+        drop(tmp);
+        assert!(!Path::new(&path).exists());
+    }
+}
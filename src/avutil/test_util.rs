@@ -0,0 +1,27 @@
+/// Lowercase hex representation of `bytes`, to compare digests against
+/// published test vectors.
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Inverse of `hex`, panicking on malformed input.
+pub fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).expect("invalid hex digit"))
+        .collect()
+}
+
+/// Registers the codecs, formats and filters enabled in this build, which
+/// FFmpeg only does by itself since 4.0.
+pub fn register_all() {
+    #[cfg(not(feature = "ffmpeg_4_0"))]
+    unsafe {
+        #[cfg(feature = "avcodec")]
+        ::avcodec_register_all();
+        #[cfg(feature = "avformat")]
+        ::av_register_all();
+        #[cfg(feature = "avfilter")]
+        ::avfilter_register_all();
+    }
+}
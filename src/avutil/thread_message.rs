@@ -0,0 +1,151 @@
+use std::marker::PhantomData;
+use std::mem::{self, MaybeUninit};
+use std::ptr;
+
+use libc::{c_uint, c_void, EINVAL};
+use {
+    av_thread_message_queue_alloc, av_thread_message_queue_free, av_thread_message_queue_recv,
+    av_thread_message_queue_send, av_thread_message_queue_set_free_func, AVThreadMessageQueue,
+};
+
+use super::error::check;
+use super::{FFmpegError, AVERROR};
+
+/// Bounded multi-producer, multi-consumer queue of `T` values on top of
+/// `AVThreadMessageQueue`.
+///
+/// Messages are moved through the queue bytewise; any still queued when it
+/// is dropped are dropped with it.
+pub struct ThreadMessageQueue<T>(*mut AVThreadMessageQueue, PhantomData<T>);
+
+// SAFETY: AVThreadMessageQueue does its own locking and is meant to be
+// shared between threads; messages move between them, hence T: Send.
+unsafe impl<T: Send> Send for ThreadMessageQueue<T> {}
+unsafe impl<T: Send> Sync for ThreadMessageQueue<T> {}
+
+unsafe extern "C" fn drop_message<T>(msg: *mut c_void) {
+    ptr::drop_in_place(msg as *mut T);
+}
+
+impl<T> ThreadMessageQueue<T> {
+    /// Fails with `AVERROR(ENOSYS)` if FFmpeg was built without threads, and
+    /// with `AVERROR(EINVAL)` for zero-sized `T`.
+    pub fn new(nelem: u32) -> Result<Self, FFmpegError> {
+        if mem::size_of::<T>() == 0 {
+            return Err(FFmpegError::Other(AVERROR(EINVAL)));
+        }
+
+        let mut mq = ptr::null_mut();
+        unsafe {
+            check(av_thread_message_queue_alloc(
+                &mut mq,
+                nelem,
+                mem::size_of::<T>() as _,
+            ))?;
+            av_thread_message_queue_set_free_func(mq, Some(drop_message::<T>));
+        }
+
+        Ok(ThreadMessageQueue(mq, PhantomData))
+    }
+
+    /// Blocks while the queue is full unless `flags` contains
+    /// `AV_THREAD_MESSAGE_NONBLOCK`. On failure `msg` is dropped.
+    pub fn send(&self, msg: T, flags: c_uint) -> Result<(), FFmpegError> {
+        let mut msg = mem::ManuallyDrop::new(msg);
+
+        unsafe {
+            let ret =
+                av_thread_message_queue_send(self.0, &mut *msg as *mut T as *mut c_void, flags);
+            if ret < 0 {
+                mem::ManuallyDrop::drop(&mut msg);
+            }
+            check(ret)?;
+        }
+
+        Ok(())
+    }
+
+    /// Blocks while the queue is empty unless `flags` contains
+    /// `AV_THREAD_MESSAGE_NONBLOCK`.
+    pub fn recv(&self, flags: c_uint) -> Result<T, FFmpegError> {
+        let mut msg = MaybeUninit::<T>::uninit();
+
+        unsafe {
+            check(av_thread_message_queue_recv(
+                self.0,
+                msg.as_mut_ptr() as *mut c_void,
+                flags,
+            ))?;
+            Ok(msg.assume_init())
+        }
+    }
+
+    pub fn as_ptr(&self) -> *mut AVThreadMessageQueue {
+        self.0
+    }
+}
+
+impl<T> Drop for ThreadMessageQueue<T> {
+    fn drop(&mut self) {
+        unsafe {
+            av_thread_message_queue_free(&mut self.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn producer_consumer() {
+        let queue = match ThreadMessageQueue::<String>::new(4) {
+            Ok(queue) => Arc::new(queue),
+            // FFmpeg built without threads
+            Err(_) => return,
+        };
+
+        let producer = {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                for i in 0..100 {
+                    queue.send(i.to_string(), 0).unwrap();
+                }
+            })
+        };
+        let consumer = {
+            let queue = queue.clone();
+            thread::spawn(move || (0..100).map(|_| queue.recv(0).unwrap()).collect::<Vec<_>>())
+        };
+
+        producer.join().unwrap();
+        let received = consumer.join().unwrap();
+        let expected: Vec<String> = (0..100).map(|i: i32| i.to_string()).collect();
+        assert_eq!(received, expected);
+    }
+
+    #[test]
+    fn drops_queued_messages() {
+        let msg = Arc::new(());
+        {
+            let queue = match ThreadMessageQueue::new(4) {
+                Ok(queue) => queue,
+                Err(_) => return,
+            };
+            queue.send(msg.clone(), 0).unwrap();
+            queue.send(msg.clone(), 0).unwrap();
+            assert_eq!(Arc::strong_count(&msg), 3);
+
+            drop(queue.recv(0).unwrap());
+            assert_eq!(Arc::strong_count(&msg), 2);
+        }
+        assert_eq!(Arc::strong_count(&msg), 1);
+    }
+
+    #[test]
+    fn zero_sized_messages() {
+        assert!(ThreadMessageQueue::<()>::new(4).is_err());
+    }
+}
@@ -6,3 +6,39 @@ pub const AV_TIME_BASE_Q: AVRational = AVRational {
     num: 1,
     den: AV_TIME_BASE as c_int,
 };
+
+/// Number of elements before the first zero-valued (`T::default()`) one, as
+/// the `av_int_list_length` macro computes it. 0 if `list` is null.
+///
+/// `list` must be terminated; lists terminated by another value, such as
+/// pixel formats ending in `AV_PIX_FMT_NONE`, need a different walk.
+pub unsafe fn int_list_length<T: Default + PartialEq>(list: *const T) -> usize {
+    if list.is_null() {
+        return 0;
+    }
+
+    let term = T::default();
+    let mut len = 0;
+    while *list.add(len) != term {
+        len += 1;
+    }
+    len
+}
+
+/// Locale-independent `"%f"` formatting, as `av_d2str` did before it was
+/// removed in FFmpeg 6.0.
+pub fn dtos(d: f64) -> String {
+    if d.is_nan() {
+        "nan".to_string()
+    } else if d.is_infinite() {
+        if d > 0.0 { "inf" } else { "-inf" }.to_string()
+    } else {
+        format!("{:.6}", d)
+    }
+}
+
+/// `value` zero-padded to at least `width` characters, sign included, as
+/// `"%0*" PRId64` would print it.
+pub fn itostr_padded(value: i64, width: usize) -> String {
+    format!("{:0width$}", value, width = width)
+}
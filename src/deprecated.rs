@@ -0,0 +1,96 @@
+//! Wrappers around bound functions that FFmpeg has deprecated, carrying
+//! `#[deprecated]` for the FFmpeg versions that deprecate them.
+//!
+//! bindgen has no hook to attach attributes to generated functions, so the
+//! bindings themselves stay unannotated; call these instead to get warnings
+//! when building against a version where the function is on its way out.
+
+#[cfg(not(feature = "ffmpeg_7_0"))]
+use libc::{c_char, c_int};
+#[cfg(feature = "avcodec")]
+use AVPacket;
+
+#[cfg(feature = "avcodec")]
+#[cfg_attr(
+    feature = "ffmpeg_5_0",
+    deprecated(since = "5.0.0", note = "use av_packet_alloc instead")
+)]
+#[inline]
+pub unsafe fn av_init_packet(pkt: *mut AVPacket) {
+    ::av_init_packet(pkt)
+}
+
+#[cfg(all(feature = "avformat", not(feature = "ffmpeg_5_0")))]
+#[cfg_attr(
+    feature = "ffmpeg_4_0",
+    deprecated(since = "4.0.0", note = "formats are registered automatically")
+)]
+#[inline]
+pub unsafe fn av_register_all() {
+    ::av_register_all()
+}
+
+#[cfg(all(feature = "avcodec", not(feature = "ffmpeg_5_0")))]
+#[cfg_attr(
+    feature = "ffmpeg_4_0",
+    deprecated(since = "4.0.0", note = "codecs are registered automatically")
+)]
+#[inline]
+pub unsafe fn avcodec_register_all() {
+    ::avcodec_register_all()
+}
+
+#[cfg(all(feature = "avfilter", not(feature = "ffmpeg_5_0")))]
+#[cfg_attr(
+    feature = "ffmpeg_4_0",
+    deprecated(since = "4.0.0", note = "filters are registered automatically")
+)]
+#[inline]
+pub unsafe fn avfilter_register_all() {
+    ::avfilter_register_all()
+}
+
+#[cfg(not(feature = "ffmpeg_7_0"))]
+#[cfg_attr(
+    feature = "ffmpeg_5_1",
+    deprecated(since = "5.1.0", note = "use av_channel_layout_from_string instead")
+)]
+#[inline]
+pub unsafe fn av_get_channel_layout(name: *const c_char) -> u64 {
+    ::av_get_channel_layout(name)
+}
+
+#[cfg(not(feature = "ffmpeg_7_0"))]
+#[cfg_attr(
+    feature = "ffmpeg_5_1",
+    deprecated(since = "5.1.0", note = "use AVChannelLayout::nb_channels instead")
+)]
+#[inline]
+pub unsafe fn av_get_channel_layout_nb_channels(channel_layout: u64) -> c_int {
+    ::av_get_channel_layout_nb_channels(channel_layout)
+}
+
+#[cfg(not(feature = "ffmpeg_7_0"))]
+#[cfg_attr(
+    feature = "ffmpeg_5_1",
+    deprecated(since = "5.1.0", note = "use av_channel_layout_describe instead")
+)]
+#[inline]
+pub unsafe fn av_get_channel_layout_string(
+    buf: *mut c_char,
+    buf_size: c_int,
+    nb_channels: c_int,
+    channel_layout: u64,
+) {
+    ::av_get_channel_layout_string(buf, buf_size, nb_channels, channel_layout)
+}
+
+#[cfg(not(feature = "ffmpeg_7_0"))]
+#[cfg_attr(
+    feature = "ffmpeg_5_1",
+    deprecated(since = "5.1.0", note = "use av_channel_layout_default instead")
+)]
+#[inline]
+pub unsafe fn av_get_default_channel_layout(nb_channels: c_int) -> i64 {
+    ::av_get_default_channel_layout(nb_channels)
+}
@@ -0,0 +1,124 @@
+use std::ptr;
+
+use libc::{c_int, EINVAL};
+use {
+    av_buffer_unref, av_hwframe_ctx_init, AVBufferRef, AVCUDADeviceContext, AVHWDeviceContext,
+    AVHWDeviceType, AVHWFramesContext, AVPixelFormat,
+};
+
+use avutil::check;
+use {alloc_hw_frames_ctx, create_hw_device_ctx, FFmpegError, AVERROR};
+
+/// Opens CUDA device `device_idx` and returns a new reference to its
+/// `AVHWDeviceContext`, to be released with `av_buffer_unref`.
+pub fn create_cuda_device_context(device_idx: c_int) -> Result<*mut AVBufferRef, FFmpegError> {
+    unsafe {
+        create_hw_device_ctx(
+            AVHWDeviceType::AV_HWDEVICE_TYPE_CUDA,
+            Some(&device_idx.to_string()),
+            None,
+            0,
+        )
+    }
+}
+
+/// The `AVCUDADeviceContext` behind a device context reference, or null if
+/// `ref_` is null or not a CUDA device.
+pub unsafe fn cuda_device_context(ref_: *mut AVBufferRef) -> *mut AVCUDADeviceContext {
+    if ref_.is_null() {
+        return ptr::null_mut();
+    }
+
+    let device_ctx = (*ref_).data as *mut AVHWDeviceContext;
+    if (*device_ctx).type_ != AVHWDeviceType::AV_HWDEVICE_TYPE_CUDA {
+        return ptr::null_mut();
+    }
+
+    (*device_ctx).hwctx as *mut AVCUDADeviceContext
+}
+
+/// Allocates and initializes an `AV_PIX_FMT_CUDA` frames context on
+/// `device_ctx`, with `fmt` as the underlying software format.
+pub unsafe fn init_cuda_frames_context(
+    device_ctx: *mut AVBufferRef,
+    fmt: AVPixelFormat,
+    width: c_int,
+    height: c_int,
+    initial_pool_size: c_int,
+) -> Result<*mut AVBufferRef, FFmpegError> {
+    if cuda_device_context(device_ctx).is_null() {
+        return Err(FFmpegError::Other(AVERROR(EINVAL)));
+    }
+
+    let mut frames_ref = alloc_hw_frames_ctx(device_ctx)?;
+
+    let frames_ctx = (*frames_ref).data as *mut AVHWFramesContext;
+    (*frames_ctx).format = AVPixelFormat::AV_PIX_FMT_CUDA;
+    (*frames_ctx).sw_format = fmt;
+    (*frames_ctx).width = width;
+    (*frames_ctx).height = height;
+    (*frames_ctx).initial_pool_size = initial_pool_size;
+
+    match check(av_hwframe_ctx_init(frames_ref)) {
+        Ok(_) => Ok(frames_ref),
+        Err(e) => {
+            av_buffer_unref(&mut frames_ref);
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use av_hwdevice_iterate_types;
+
+    fn cuda_available() -> bool {
+        let mut type_ = AVHWDeviceType::AV_HWDEVICE_TYPE_NONE;
+        loop {
+            type_ = unsafe { av_hwdevice_iterate_types(type_) };
+            match type_ {
+                AVHWDeviceType::AV_HWDEVICE_TYPE_NONE => return false,
+                AVHWDeviceType::AV_HWDEVICE_TYPE_CUDA => return true,
+                _ => (),
+            }
+        }
+    }
+
+    #[test]
+    fn not_a_cuda_device() {
+        unsafe {
+            assert!(cuda_device_context(ptr::null_mut()).is_null());
+            assert!(init_cuda_frames_context(
+                ptr::null_mut(),
+                AVPixelFormat::AV_PIX_FMT_NV12,
+                64,
+                64,
+                0
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn cuda_frames_context() {
+        // needs libavutil built with CUDA support and an actual device
+        if !cuda_available() {
+            return;
+        }
+        let mut device = match create_cuda_device_context(0) {
+            Ok(device) => device,
+            Err(_) => return,
+        };
+
+        unsafe {
+            assert!(!cuda_device_context(device).is_null());
+
+            let mut frames =
+                init_cuda_frames_context(device, AVPixelFormat::AV_PIX_FMT_NV12, 64, 64, 0)
+                    .unwrap();
+            av_buffer_unref(&mut frames);
+            av_buffer_unref(&mut device);
+        }
+    }
+}
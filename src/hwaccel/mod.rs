@@ -0,0 +1,4 @@
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+mod cuda;
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+pub use self::cuda::*;
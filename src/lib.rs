@@ -7,10 +7,51 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::type_complexity)]
 
+#[macro_use]
+extern crate bitflags;
+#[cfg(feature = "digest")]
+extern crate digest;
 extern crate libc;
+#[cfg(feature = "log")]
+extern crate log;
+#[cfg(feature = "rand-core")]
+extern crate rand_core;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(all(test, feature = "tracing"))]
+extern crate tracing_subscriber;
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
+#[path = "safety.rs"]
+pub mod safety_notes;
+
+pub mod deprecated;
+
+mod version;
+pub use version::*;
+
 #[macro_use]
 mod avutil;
 pub use avutil::*;
+
+#[cfg(feature = "avfilter")]
+mod avfilter;
+#[cfg(feature = "avfilter")]
+pub use avfilter::*;
+
+#[cfg(feature = "avcodec")]
+mod avcodec;
+#[cfg(feature = "avcodec")]
+pub use avcodec::*;
+
+// builds on the generic hwcontext helpers, which need FFmpeg 4.0
+#[cfg(all(feature = "hwcontext-cuda", feature = "ffmpeg_4_0"))]
+mod hwaccel;
+#[cfg(all(feature = "hwcontext-cuda", feature = "ffmpeg_4_0"))]
+pub use hwaccel::*;
+
+#[cfg(feature = "avformat")]
+mod avformat;
+#[cfg(feature = "avformat")]
+pub use avformat::*;
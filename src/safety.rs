@@ -0,0 +1,48 @@
+//! Thread-safety notes for the FFmpeg types exposed by this crate.
+//!
+//! The generated bindings only hand out raw pointers, which are neither
+//! `Send` nor `Sync`. What FFmpeg actually guarantees, as far as its
+//! documentation goes:
+//!
+//! - `AVFormatContext`: no internal locking. A context may be moved to another
+//!   thread, but every call taking it must be serialized by the caller. The
+//!   only exception is the `interrupt_callback`, which is invoked from the
+//!   thread doing I/O.
+//! - `AVCodecContext`: same as `AVFormatContext` from the API side. Frame and
+//!   slice threading (`FF_THREAD_FRAME`/`FF_THREAD_SLICE` in `thread_type`)
+//!   are handled by worker threads inside libavcodec and do not make it safe
+//!   to call into the same context concurrently. Callbacks such as
+//!   `get_buffer2` and `get_format` may be called from those worker threads.
+//! - `AVBufferRef`: the reference count of the underlying `AVBuffer` is
+//!   atomic, so distinct references to one buffer can be created, used and
+//!   released from different threads. A single `AVBufferRef` is not
+//!   synchronized, and the data is only safe to share while nobody writes to
+//!   it (see `av_buffer_is_writable`/`av_buffer_make_writable`).
+//! - `AVFrame`/`AVPacket`: plain structs around `AVBufferRef`s, movable
+//!   between threads; sharing the data follows the `AVBufferRef` rules.
+//! - `AVFilterGraph`: like `AVCodecContext`, its `nb_threads`/`execute`
+//!   threading is internal, calls on one graph must be serialized.
+//! - `SwsContext`/`SwrContext`: hold per-conversion scratch state, one context
+//!   must not be used by several threads at once.
+//! - Logging: the callback set with `av_log_set_callback` can be invoked from
+//!   any thread, including libavcodec's worker threads, and must be
+//!   thread-safe.
+//!
+//! Following the above, the wrappers around contexts that have no thread
+//! affinity implement `Send` but not `Sync` (e.g. the hashers and ciphers
+//! in `avutil`), while types that are only read through `&self` (e.g.
+//! `ChannelLayout`) also implement `Sync`. Each such implementation carries
+//! a comment of the form:
+//!
+//! ```text
+//! // SAFETY: <context type> has no thread affinity and is only accessed
+//! // through <wrapper>, which requires &mut self for every operation
+//! // mutating it; see the safety_notes module.
+//! unsafe impl Send for Wrapper {}
+//! ```
+//!
+//! `AVFormatContext` and `AVCodecContext` are the exception: they call back
+//! into user code (`interrupt_callback`, `get_buffer2`, `get_format`, ...)
+//! with user-set `opaque` pointers, which the wrappers cannot check. Their
+//! `Send` implementations assume that whatever is installed there through
+//! the raw pointer is itself safe to use from another thread.
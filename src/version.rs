@@ -0,0 +1,42 @@
+use std::ffi::CStr;
+
+use libc::c_char;
+use {av_version_info, avutil_configuration, avutil_license};
+
+unsafe fn static_str(s: *const c_char) -> &'static str {
+    CStr::from_ptr(s).to_str().unwrap_or("")
+}
+
+/// FFmpeg version string, e.g. `"7.1"` for a release or `"N-<commit>"` for a
+/// git snapshot.
+pub fn version_info() -> &'static str {
+    unsafe { static_str(av_version_info()) }
+}
+
+/// `configure` command line FFmpeg was built with. All libraries of one
+/// build report the same string, libavutil's is returned here.
+pub fn build_config() -> &'static str {
+    unsafe { static_str(avutil_configuration()) }
+}
+
+pub fn ffmpeg_license() -> &'static str {
+    unsafe { static_str(avutil_license()) }
+}
+
+/// Whether FFmpeg was built with debug symbols. `configure` enables them by
+/// default, so only an explicit `--disable-debug` counts against it.
+pub fn is_debug_build() -> bool {
+    build_config()
+        .split_whitespace()
+        .rev()
+        .find_map(|arg| {
+            if arg.starts_with("--enable-debug") {
+                Some(true)
+            } else if arg == "--disable-debug" {
+                Some(false)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(true)
+}